@@ -0,0 +1,430 @@
+mod portfolio;
+mod types;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    marker::PhantomData
+};
+
+use alloy_eips::BlockId;
+use alloy_network::Network;
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::SolEvent;
+use angstrom_types_primitives::primitive::PoolId;
+use futures::{StreamExt, TryStreamExt, future::try_join_all};
+use op_alloy_network::Optimism;
+pub use portfolio::{Portfolio, PortfolioPosition};
+pub use types::*;
+use uniswap_storage::v4::UnpackedSlot0;
+
+use crate::{
+    l1::{
+        AngstromL1Chain,
+        apis::{AngstromL1DataApi, AngstromL1UserApi},
+        types::PositionTransfer
+    },
+    l2::{
+        AngstromL2Chain,
+        apis::{AngstromL2DataApi, AngstromL2UserApi}
+    },
+    types::{fees::LiquidityPositionFees, utils::historical_position_transfers_filter}
+};
+
+/// How many log or position requests are in flight at once.
+const REQUEST_BUFFER: usize = 100;
+
+/// A chain with an angstrom deployment.
+#[derive(Debug, Clone, Copy)]
+pub enum AngstromChain {
+    L1(AngstromL1Chain),
+    L2(AngstromL2Chain)
+}
+
+impl AngstromChain {
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            AngstromChain::L1(chain) => chain.chain_id(),
+            AngstromChain::L2(chain) => chain.chain_id()
+        }
+    }
+
+    /// The built-in chain with the chain id, if any.
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        AngstromL1Chain::from_chain_id(chain_id)
+            .map(AngstromChain::L1)
+            .or_else(|| AngstromL2Chain::from_chain_id(chain_id).map(AngstromChain::L2))
+    }
+}
+
+/// The provider of one chain added to [`AngstromAnyChain`].
+enum Deployment<'a, P1, P2> {
+    L1(&'a P1, AngstromL1Chain),
+    L2(&'a P2, AngstromL2Chain)
+}
+
+/// One api over the l1 and l2 deployments, with a provider for each chain.
+/// Calls take the chain id of the deployment to read and are dispatched to
+/// [`AngstromL1DataApi`] or [`AngstromL2DataApi`], returning the same types
+/// for either.
+#[derive(Debug, Clone)]
+pub struct AngstromAnyChain<P1, P2, N: Network = Optimism> {
+    l1:       BTreeMap<u64, (P1, AngstromL1Chain)>,
+    l2:       BTreeMap<u64, (P2, AngstromL2Chain)>,
+    _network: PhantomData<N>
+}
+
+impl<P1, P2, N: Network> Default for AngstromAnyChain<P1, P2, N> {
+    fn default() -> Self {
+        Self { l1: BTreeMap::new(), l2: BTreeMap::new(), _network: PhantomData }
+    }
+}
+
+impl<P1, P2, N: Network> AngstromAnyChain<P1, P2, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the l1 deployment, replacing any on the same chain. The provider
+    /// is assumed to be connected to `chain`.
+    pub fn with_l1(mut self, provider: P1, chain: AngstromL1Chain) -> Self {
+        self.l1.insert(chain.chain_id(), (provider, chain));
+        self
+    }
+
+    /// Adds the l2 deployment, replacing any on the same chain. The provider
+    /// is assumed to be connected to `chain`.
+    pub fn with_l2(mut self, provider: P2, chain: AngstromL2Chain) -> Self {
+        self.l2.insert(chain.chain_id(), (provider, chain));
+        self
+    }
+
+    pub fn chains(&self) -> impl Iterator<Item = AngstromChain> + '_ {
+        self.l1
+            .values()
+            .map(|(_, chain)| AngstromChain::L1(*chain))
+            .chain(self.l2.values().map(|(_, chain)| AngstromChain::L2(*chain)))
+    }
+
+    pub fn chain(&self, chain_id: u64) -> Option<AngstromChain> {
+        self.chains().find(|chain| chain.chain_id() == chain_id)
+    }
+
+    pub fn l1_provider(&self, chain_id: u64) -> Option<&P1> {
+        self.l1.get(&chain_id).map(|(provider, _)| provider)
+    }
+
+    pub fn l2_provider(&self, chain_id: u64) -> Option<&P2> {
+        self.l2.get(&chain_id).map(|(provider, _)| provider)
+    }
+
+    fn deployment(&self, chain_id: u64) -> eyre::Result<Deployment<'_, P1, P2>> {
+        if let Some((provider, chain)) = self.l1.get(&chain_id) {
+            Ok(Deployment::L1(provider, *chain))
+        } else if let Some((provider, chain)) = self.l2.get(&chain_id) {
+            Ok(Deployment::L2(provider, *chain))
+        } else {
+            Err(eyre::eyre!("no provider for chain {chain_id}"))
+        }
+    }
+}
+
+impl<P1, P2, N> AngstromAnyChain<P1, P2, N>
+where
+    P1: AngstromL1DataApi,
+    P2: AngstromL2DataApi<N>,
+    N: Network
+{
+    /// Adds the l1 deployment of the chain the provider is connected to.
+    pub async fn with_detected_l1(self, provider: P1) -> eyre::Result<Self> {
+        let chain = AngstromL1Chain::from_provider(&provider.alloy_root_provider().await?).await?;
+        Ok(self.with_l1(provider, chain))
+    }
+
+    /// Adds the l2 deployment of the chain the provider is connected to.
+    pub async fn with_detected_l2(self, provider: P2) -> eyre::Result<Self> {
+        let chain = AngstromL2Chain::from_provider(&provider.alloy_root_provider().await?).await?;
+        Ok(self.with_l2(provider, chain))
+    }
+
+    pub async fn all_pools(
+        &self,
+        chain_id: u64,
+        block_id: BlockId
+    ) -> eyre::Result<Vec<AnyPoolKey>> {
+        Ok(match self.deployment(chain_id)? {
+            Deployment::L1(provider, chain) => {
+                AngstromL1DataApi::all_pool_keys(provider, block_id, chain)
+                    .await?
+                    .into_iter()
+                    .map(|pool_key| AnyPoolKey::from_l1(chain_id, pool_key))
+                    .collect()
+            }
+            Deployment::L2(provider, chain) => {
+                AngstromL2DataApi::<N>::all_pool_keys(provider, block_id, chain)
+                    .await?
+                    .into_iter()
+                    .map(|pool_key| AnyPoolKey::from_l2(chain_id, pool_key))
+                    .collect()
+            }
+        })
+    }
+
+    pub async fn pool_by_pool_id(
+        &self,
+        chain_id: u64,
+        pool_id: PoolId,
+        block_id: BlockId
+    ) -> eyre::Result<AnyPoolKey> {
+        Ok(match self.deployment(chain_id)? {
+            Deployment::L1(provider, chain) => AnyPoolKey::from_l1(
+                chain_id,
+                AngstromL1DataApi::pool_key_by_pool_id(provider, pool_id, block_id, chain).await?
+            ),
+            Deployment::L2(provider, chain) => AnyPoolKey::from_l2(
+                chain_id,
+                AngstromL2DataApi::<N>::pool_key_by_pool_id(provider, pool_id, block_id, chain)
+                    .await?
+            )
+        })
+    }
+
+    pub async fn fee_configuration(
+        &self,
+        chain_id: u64,
+        pool_id: PoolId,
+        block_id: BlockId
+    ) -> eyre::Result<AnyFeeConfiguration> {
+        Ok(match self.deployment(chain_id)? {
+            Deployment::L1(provider, chain) => AnyFeeConfiguration::L1(
+                AngstromL1DataApi::fee_configuration_by_pool_id(provider, pool_id, block_id, chain)
+                    .await?
+            ),
+            Deployment::L2(provider, chain) => AnyFeeConfiguration::L2(
+                AngstromL2DataApi::<N>::fee_configuration_by_pool_id(
+                    provider, pool_id, block_id, chain
+                )
+                .await?
+            )
+        })
+    }
+
+    /// The owner's angstrom positions on the chain at `block_id`, from the
+    /// PositionManager tokens transferred to it.
+    pub async fn user_positions(
+        &self,
+        chain_id: u64,
+        owner: Address,
+        max_results: Option<usize>,
+        block_id: BlockId
+    ) -> eyre::Result<Vec<AnyPosition>> {
+        let token_ids = self.owner_token_ids(chain_id, owner, block_id).await?;
+        let positions = match self.deployment(chain_id)? {
+            Deployment::L1(provider, chain) => {
+                futures::stream::iter(token_ids)
+                    .map(|token_id| {
+                        AngstromL1UserApi::all_user_positions(
+                            provider, owner, token_id, token_id, None, None, block_id, chain
+                        )
+                    })
+                    .buffered(REQUEST_BUFFER)
+                    .try_collect::<Vec<_>>()
+                    .await?
+            }
+            Deployment::L2(provider, chain) => {
+                futures::stream::iter(token_ids)
+                    .map(|token_id| {
+                        AngstromL2UserApi::<N>::all_user_positions(
+                            provider, owner, token_id, token_id, None, None, block_id, chain
+                        )
+                    })
+                    .buffered(REQUEST_BUFFER)
+                    .try_collect::<Vec<_>>()
+                    .await?
+            }
+        };
+
+        Ok(positions
+            .into_iter()
+            .flatten()
+            .take(max_results.unwrap_or(usize::MAX))
+            .map(|position| AnyPosition::new(chain_id, position))
+            .collect())
+    }
+
+    /// The ids of the PositionManager tokens transferred to the owner since
+    /// angstrom's deployment, up to `block_id`. The owner may have since
+    /// transferred some of them away.
+    async fn owner_token_ids(
+        &self,
+        chain_id: u64,
+        owner: Address,
+        block_id: BlockId
+    ) -> eyre::Result<BTreeSet<U256>> {
+        let end_block = self.block_number(chain_id, block_id).await?;
+        let deployment = self.deployment(chain_id)?;
+        let (position_manager, deploy_block) = match deployment {
+            Deployment::L1(_, chain) => (
+                chain.constants().uniswap_constants().position_manager(),
+                chain.constants().angstrom_deploy_block()
+            ),
+            Deployment::L2(_, chain) => (
+                chain.constants().uniswap_constants().position_manager(),
+                chain.constants().angstrom_deploy_block()
+            )
+        };
+        let filters = historical_position_transfers_filter(
+            None,
+            Some(end_block),
+            position_manager,
+            owner,
+            deploy_block
+        );
+
+        let logs = match deployment {
+            Deployment::L1(provider, _) => {
+                futures::stream::iter(&filters)
+                    .map(|filter| provider.fetch_logs_primitive(filter))
+                    .buffer_unordered(REQUEST_BUFFER)
+                    .try_collect::<Vec<_>>()
+                    .await?
+            }
+            Deployment::L2(provider, _) => {
+                futures::stream::iter(&filters)
+                    .map(|filter| provider.fetch_logs_primitive(filter))
+                    .buffer_unordered(REQUEST_BUFFER)
+                    .try_collect::<Vec<_>>()
+                    .await?
+            }
+        };
+
+        Ok(logs
+            .into_iter()
+            .flatten()
+            .filter_map(|log| PositionTransfer::decode_log(&log.inner).ok())
+            .map(|transfer| transfer.id)
+            .collect())
+    }
+
+    pub async fn user_position_fees(
+        &self,
+        chain_id: u64,
+        position_token_id: U256,
+        block_id: BlockId
+    ) -> eyre::Result<LiquidityPositionFees> {
+        match self.deployment(chain_id)? {
+            Deployment::L1(provider, chain) => {
+                AngstromL1UserApi::user_position_fees(provider, position_token_id, block_id, chain)
+                    .await
+            }
+            Deployment::L2(provider, chain) => {
+                AngstromL2UserApi::<N>::user_position_fees(
+                    provider,
+                    position_token_id,
+                    block_id,
+                    chain
+                )
+                .await
+            }
+        }
+    }
+
+    pub async fn slot0_by_pool_id(
+        &self,
+        chain_id: u64,
+        pool_id: PoolId,
+        block_id: BlockId
+    ) -> eyre::Result<UnpackedSlot0> {
+        match self.deployment(chain_id)? {
+            Deployment::L1(provider, chain) => {
+                AngstromL1DataApi::slot0_by_pool_id(provider, pool_id, block_id, chain).await
+            }
+            Deployment::L2(provider, chain) => {
+                AngstromL2DataApi::<N>::slot0_by_pool_id(provider, pool_id, block_id, chain).await
+            }
+        }
+    }
+
+    pub async fn block_number(&self, chain_id: u64, block_id: BlockId) -> eyre::Result<u64> {
+        match self.deployment(chain_id)? {
+            Deployment::L1(provider, _) => provider.block_number_from_block_id(block_id).await,
+            Deployment::L2(provider, _) => provider.block_number_from_block_id(block_id).await
+        }
+    }
+
+    /// The pools of every chain at its latest block.
+    pub async fn all_pools_on_all_chains(&self) -> eyre::Result<Vec<AnyPoolKey>> {
+        let pools = try_join_all(
+            self.chains()
+                .map(|chain| self.all_pools(chain.chain_id(), BlockId::latest()))
+        )
+        .await?;
+
+        Ok(pools.into_iter().flatten().collect())
+    }
+
+    /// The owner's positions on every chain at its latest block.
+    pub async fn user_positions_on_all_chains(
+        &self,
+        owner: Address
+    ) -> eyre::Result<Vec<AnyPosition>> {
+        let positions =
+            try_join_all(self.chains().map(|chain| {
+                self.user_positions(chain.chain_id(), owner, None, BlockId::latest())
+            }))
+            .await?;
+
+        Ok(positions.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_chain_id() {
+        assert!(matches!(
+            AngstromChain::from_chain_id(1),
+            Some(AngstromChain::L1(AngstromL1Chain::Mainnet))
+        ));
+        assert!(matches!(
+            AngstromChain::from_chain_id(8453),
+            Some(AngstromChain::L2(AngstromL2Chain::Base))
+        ));
+        assert!(AngstromChain::from_chain_id(10).is_none());
+    }
+
+    #[cfg(not(feature = "local-reth"))]
+    #[tokio::test]
+    async fn test_all_pools_on_all_chains() {
+        use crate::{
+            l1::test_utils::valid_test_params::init_valid_position_params_with_provider,
+            l2::test_utils::spawn_l2_provider
+        };
+
+        let (l1_provider, _) = init_valid_position_params_with_provider().await;
+        let (l2_provider, l2_chain) = spawn_l2_provider().await.unwrap();
+        let api = AngstromAnyChain::<_, _, Optimism>::new()
+            .with_detected_l1(l1_provider)
+            .await
+            .unwrap()
+            .with_l2(l2_provider, l2_chain);
+
+        let pools = api.all_pools_on_all_chains().await.unwrap();
+        assert!(pools.iter().any(|pool| pool.chain_id == 1));
+        assert!(
+            pools
+                .iter()
+                .any(|pool| pool.chain_id == l2_chain.chain_id())
+        );
+
+        let pool = pools
+            .iter()
+            .find(|pool| pool.chain_id == l2_chain.chain_id())
+            .unwrap();
+        let fees = api
+            .fee_configuration(pool.chain_id, pool.pool_id, BlockId::latest())
+            .await
+            .unwrap();
+        assert!(matches!(fees, AnyFeeConfiguration::L2(_)));
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use alloy_eips::BlockId;
+use alloy_network::Network;
+use alloy_primitives::{Address, U256};
+use angstrom_rpc_types::PendingOrder;
+use futures::future::try_join_all;
+
+use super::{AngstromAnyChain, AnyPoolKey, AnyPosition};
+use crate::{
+    l1::apis::{AngstromL1DataApi, AngstromNodeApi, AngstromOrderApiClient},
+    l2::apis::AngstromL2DataApi,
+    types::{fees::LiquidityPositionFees, liquidity_amounts::amounts_for_liquidity}
+};
+
+/// An angstrom liquidity position with the tokens it holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortfolioPosition {
+    pub position:     AnyPosition,
+    pub pool:         AnyPoolKey,
+    /// the block of the chain the position was read at
+    pub block_number: u64,
+    /// The tokens the position's liquidity is worth at the pool's current
+    /// price, without its uncollected fees.
+    pub amount0:      U256,
+    pub amount1:      U256,
+    pub fees:         LiquidityPositionFees
+}
+
+/// Everything an owner has in angstrom across the chains.
+#[derive(Debug, Clone)]
+pub struct Portfolio {
+    pub owner:          Address,
+    pub positions:      Vec<PortfolioPosition>,
+    /// orders waiting to be included in a bundle on l1
+    pub pending_orders: Vec<PendingOrder>
+}
+
+impl<P1, P2, N> AngstromAnyChain<P1, P2, N>
+where
+    P1: AngstromL1DataApi,
+    P2: AngstromL2DataApi<N>,
+    N: Network
+{
+    /// The owner's positions and pending orders on every chain.
+    pub async fn portfolio<T>(&self, owner: Address) -> eyre::Result<Portfolio>
+    where
+        P1: AngstromNodeApi<T>,
+        T: AngstromOrderApiClient
+    {
+        let pending_orders = async {
+            let orders = try_join_all(
+                self.l1
+                    .values()
+                    .map(|(provider, _)| provider.pending_order(owner))
+            )
+            .await?;
+            eyre::Ok(orders.into_iter().flatten().collect::<Vec<_>>())
+        };
+        let (positions, pending_orders) =
+            tokio::try_join!(self.portfolio_positions(owner), pending_orders)?;
+
+        Ok(Portfolio { owner, positions, pending_orders })
+    }
+
+    /// The owner's positions on every chain, each read at its chain's latest
+    /// block.
+    pub async fn portfolio_positions(
+        &self,
+        owner: Address
+    ) -> eyre::Result<Vec<PortfolioPosition>> {
+        let positions = try_join_all(
+            self.chains()
+                .map(|chain| self.chain_portfolio_positions(chain.chain_id(), owner))
+        )
+        .await?;
+
+        Ok(positions.into_iter().flatten().collect())
+    }
+
+    /// The tokens and uncollected fees of the position.
+    pub async fn portfolio_position(
+        &self,
+        position: AnyPosition,
+        pool: AnyPoolKey,
+        block_number: u64
+    ) -> eyre::Result<PortfolioPosition> {
+        let block_id = BlockId::from(block_number);
+        let (slot0, fees) = tokio::try_join!(
+            self.slot0_by_pool_id(position.chain_id, position.pool_id, block_id),
+            self.user_position_fees(position.chain_id, position.token_id, block_id)
+        )?;
+
+        let (amount0, amount1) = amounts_for_liquidity(
+            U256::from(slot0.sqrt_price_x96),
+            position.tick_lower.as_i32(),
+            position.tick_upper.as_i32(),
+            position.liquidity
+        );
+
+        Ok(PortfolioPosition { position, pool, block_number, amount0, amount1, fees })
+    }
+
+    async fn chain_portfolio_positions(
+        &self,
+        chain_id: u64,
+        owner: Address
+    ) -> eyre::Result<Vec<PortfolioPosition>> {
+        let block_number = self.block_number(chain_id, BlockId::latest()).await?;
+        let (positions, pools) = tokio::try_join!(
+            self.user_positions(chain_id, owner, None, block_number.into()),
+            self.all_pools(chain_id, block_number.into())
+        )?;
+        let pools = pools
+            .into_iter()
+            .map(|pool| (pool.pool_id, pool))
+            .collect::<HashMap<_, _>>();
+
+        try_join_all(positions.into_iter().map(|position| {
+            let pool = pools.get(&position.pool_id).copied();
+            async move {
+                let pool = pool.ok_or_else(|| {
+                    eyre::eyre!("no pool {:?} on chain {chain_id}", position.pool_id)
+                })?;
+                self.portfolio_position(position, pool, block_number).await
+            }
+        }))
+        .await
+    }
+}
+
+#[cfg(all(test, not(feature = "local-reth")))]
+mod tests {
+    use op_alloy_network::Optimism;
+
+    use super::*;
+    use crate::{
+        l1::{
+            AngstromL1Chain, apis::AngstromL1UserApi,
+            test_utils::valid_test_params::init_valid_position_params_with_provider
+        },
+        types::providers::AlloyProviderWrapper
+    };
+
+    #[tokio::test]
+    async fn test_portfolio_position() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+        let api = AngstromAnyChain::<_, AlloyProviderWrapper<Optimism>, Optimism>::new()
+            .with_l1(provider.clone(), AngstromL1Chain::Mainnet);
+
+        let pool_id = state.pool_key.into();
+        let position = AnyPosition {
+            chain_id: 1,
+            token_id: state.position_token_id,
+            pool_id,
+            tick_lower: state.tick_lower,
+            tick_upper: state.tick_upper,
+            liquidity: state.position_liquidity
+        };
+        let pool = api
+            .pool_by_pool_id(1, pool_id, state.block_number.into())
+            .await
+            .unwrap();
+
+        let overview = api
+            .portfolio_position(position, pool, state.block_number)
+            .await
+            .unwrap();
+        let fees = provider
+            .user_position_fees(
+                state.position_token_id,
+                state.block_number.into(),
+                AngstromL1Chain::Mainnet
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(overview.fees, fees);
+        assert!(overview.amount0 > U256::ZERO || overview.amount1 > U256::ZERO);
+    }
+}
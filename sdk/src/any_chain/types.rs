@@ -0,0 +1,86 @@
+use alloy_primitives::{
+    Address, U256,
+    aliases::{I24, U24}
+};
+use angstrom_types_primitives::primitive::PoolId;
+use serde::{Deserialize, Serialize};
+use uni_v4::{L1FeeConfiguration, L2FeeConfiguration};
+use uniswap_storage::v4::V4UserLiquidityPosition;
+
+use crate::types::{
+    common::PoolKeyWithAngstromFee, contracts::angstrom_l2::angstrom_l_2_factory::AngstromL2Factory
+};
+
+/// An angstrom pool on any chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AnyPoolKey {
+    pub chain_id:      u64,
+    pub pool_id:       PoolId,
+    pub currency0:     Address,
+    pub currency1:     Address,
+    pub fee:           U24,
+    pub tick_spacing:  I24,
+    pub hooks:         Address,
+    /// l1 pools only
+    pub bundle_fee_e6: Option<U24>
+}
+
+impl AnyPoolKey {
+    pub fn from_l1(chain_id: u64, pool_key: PoolKeyWithAngstromFee) -> Self {
+        Self {
+            chain_id,
+            pool_id: PoolId::from(pool_key),
+            currency0: pool_key.pool_key.currency0,
+            currency1: pool_key.pool_key.currency1,
+            fee: pool_key.pool_key.fee,
+            tick_spacing: pool_key.pool_key.tickSpacing,
+            hooks: pool_key.pool_key.hooks,
+            bundle_fee_e6: Some(pool_key.pool_fee_in_e6)
+        }
+    }
+
+    pub fn from_l2(chain_id: u64, pool_key: AngstromL2Factory::PoolKey) -> Self {
+        Self {
+            chain_id,
+            pool_id: PoolId::from(pool_key),
+            currency0: pool_key.currency0,
+            currency1: pool_key.currency1,
+            fee: pool_key.fee,
+            tick_spacing: pool_key.tickSpacing,
+            hooks: pool_key.hooks,
+            bundle_fee_e6: None
+        }
+    }
+}
+
+/// The fee configuration of a pool on any chain. L1 and l2 pools charge
+/// different fees, so they are kept as they are read.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnyFeeConfiguration {
+    L1(L1FeeConfiguration),
+    L2(L2FeeConfiguration)
+}
+
+/// An angstrom liquidity position on any chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AnyPosition {
+    pub chain_id:   u64,
+    pub token_id:   U256,
+    pub pool_id:    PoolId,
+    pub tick_lower: I24,
+    pub tick_upper: I24,
+    pub liquidity:  u128
+}
+
+impl AnyPosition {
+    pub fn new(chain_id: u64, position: V4UserLiquidityPosition) -> Self {
+        Self {
+            chain_id,
+            token_id: position.token_id,
+            pool_id: PoolId::from(position.pool_key),
+            tick_lower: position.tick_lower,
+            tick_upper: position.tick_upper,
+            liquidity: position.liquidity
+        }
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockId;
+use alloy_network::BlockResponse;
+use alloy_primitives::U256;
+use angstrom_types_primitives::{contract_bindings::pool_manager::PoolManager, primitive::PoolId};
+use futures::StreamExt;
+use itertools::Itertools;
+use uni_v4::{
+    bindings::get_uniswap_v_4_pool_data::GetUniswapV4PoolData, pool_data_loader::PoolDataV4
+};
+
+use super::data_api::AngstromL1DataApi;
+use crate::{
+    l1::{
+        AngstromL1Chain,
+        types::{
+            Candle, CandleBuilder, DecodedBundle, PoolAnalyticsAggregator, PoolAnalyticsBucket
+        }
+    },
+    types::{
+        common::BucketInterval,
+        pool_stats::{PoolApr, rank_by_apr}
+    }
+};
+
+impl<P> AngstromL1AnalyticsApi for P where P: AngstromL1DataApi {}
+
+#[async_trait::async_trait]
+pub trait AngstromL1AnalyticsApi: AngstromL1DataApi {
+    /// Aggregates the bundles and post-bundle unlocked swaps between
+    /// `start_block` and `end_block` per pool and per `interval` bucket.
+    ///
+    /// Fees of unlocked swaps are computed with each pool's fee
+    /// configuration at `end_block`.
+    async fn pool_analytics(
+        &self,
+        start_block: Option<u64>,
+        end_block: Option<u64>,
+        interval: BucketInterval,
+        block_stream_buffer: Option<usize>,
+        chain: AngstromL1Chain
+    ) -> eyre::Result<Vec<PoolAnalyticsBucket>> {
+        let end_block_id = end_block.map(Into::into).unwrap_or_else(BlockId::latest);
+
+        let (bundles, unlocked_swaps, pool_keys) = tokio::try_join!(
+            self.historical_bundles(start_block, end_block, block_stream_buffer, chain),
+            self.historical_post_bundle_unlock_swaps(start_block, end_block, chain),
+            self.all_pool_keys(end_block_id, chain)
+        )?;
+
+        let pools = futures::future::try_join_all(pool_keys.into_iter().map(async |key| {
+            let fee_config = self
+                .fee_configuration_by_tokens(
+                    key.pool_key.currency0,
+                    key.pool_key.currency1,
+                    Some(key.pool_fee_in_e6),
+                    end_block_id,
+                    chain
+                )
+                .await?;
+
+            Ok::<_, eyre::ErrReport>((
+                PoolId::from(key.pool_key),
+                key.pool_key.currency0,
+                key.pool_key.currency1,
+                fee_config
+            ))
+        }))
+        .await?;
+
+        let timestamps = if interval.needs_timestamps() {
+            let blocks = bundles
+                .iter()
+                .filter_map(|bundle| bundle.block_number)
+                .chain(unlocked_swaps.iter().filter_map(|swap| swap.block_number));
+            self.block_timestamps(blocks, block_stream_buffer).await?
+        } else {
+            HashMap::default()
+        };
+        let timestamp =
+            |block_number: u64| timestamps.get(&block_number).copied().unwrap_or_default();
+
+        let mut aggregator = PoolAnalyticsAggregator::new(interval, pools);
+
+        let bundles = bundles
+            .into_iter()
+            .filter_map(|bundle| Some((bundle.block_number?, bundle.tx_idx, bundle.inner)))
+            .sorted_by_key(|(block_number, tx_idx, _)| (*block_number, *tx_idx));
+        for (block_number, _, bundle) in bundles {
+            aggregator
+                .add_bundle(&DecodedBundle::new(&bundle, block_number)?, timestamp(block_number));
+        }
+
+        let unlocked_swaps = unlocked_swaps
+            .into_iter()
+            .filter_map(|swap| Some((swap.block_number?, swap.tx_idx, swap.inner)))
+            .sorted_by_key(|(block_number, tx_idx, _)| (*block_number, *tx_idx));
+        for (block_number, _, swap) in unlocked_swaps {
+            aggregator.add_unlocked_swap(&swap, block_number, timestamp(block_number));
+        }
+
+        Ok(aggregator.finish())
+    }
+
+    /// OHLCV candles of the pool between `from_block` and `to_block`, from
+    /// the bundle clearing prices and the post-bundle unlocked swap prices.
+    async fn candles(
+        &self,
+        pool_id: PoolId,
+        interval: BucketInterval,
+        from_block: u64,
+        to_block: u64,
+        chain: AngstromL1Chain
+    ) -> eyre::Result<Vec<Candle>> {
+        let pool_key = self
+            .pool_key_by_pool_id(pool_id, to_block.into(), chain)
+            .await?;
+        let (token0, token1) = (pool_key.pool_key.currency0, pool_key.pool_key.currency1);
+
+        let data_deployer_call = GetUniswapV4PoolData::deploy_builder(
+            &self.alloy_root_provider().await?,
+            pool_id,
+            chain.constants().uniswap_constants().pool_manager(),
+            token0,
+            token1
+        )
+        .into_transaction_request();
+
+        let (pool_data, bundles, unlocked_swaps) = tokio::try_join!(
+            self.view_deploy_call::<PoolDataV4>(to_block.into(), data_deployer_call),
+            self.historical_bundles(Some(from_block), Some(to_block), None, chain),
+            self.historical_post_bundle_unlock_swaps(Some(from_block), Some(to_block), chain)
+        )?;
+
+        let timestamps = if interval.needs_timestamps() {
+            let blocks = bundles
+                .iter()
+                .filter_map(|bundle| bundle.block_number)
+                .chain(unlocked_swaps.iter().filter_map(|swap| swap.block_number));
+            self.block_timestamps(blocks, None).await?
+        } else {
+            HashMap::default()
+        };
+        let timestamp =
+            |block_number: u64| timestamps.get(&block_number).copied().unwrap_or_default();
+
+        let mut builder =
+            CandleBuilder::new(interval, pool_data.tokenADecimals, pool_data.tokenBDecimals);
+
+        enum Trade {
+            Bundle(DecodedBundle),
+            Swap(Box<PoolManager::Swap>)
+        }
+
+        let bundles = bundles
+            .into_iter()
+            .filter_map(|bundle| Some((bundle.block_number?, bundle)))
+            .map(|(block_number, bundle)| {
+                let decoded = DecodedBundle::new(&bundle.inner, block_number)?;
+                eyre::Ok((block_number, bundle.tx_idx, Trade::Bundle(decoded)))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let unlocked_swaps = unlocked_swaps
+            .into_iter()
+            .filter(|swap| swap.inner.id == pool_id)
+            .filter_map(|swap| {
+                Some((swap.block_number?, swap.tx_idx, Trade::Swap(Box::new(swap.inner))))
+            });
+
+        let trades = bundles
+            .into_iter()
+            .chain(unlocked_swaps)
+            .sorted_by_key(|(block_number, tx_idx, _)| (*block_number, *tx_idx));
+        for (block_number, _, trade) in trades {
+            match trade {
+                Trade::Bundle(bundle) => {
+                    let fills = bundle
+                        .all_fills()
+                        .filter(|fill| {
+                            (fill.token_in == token0 && fill.token_out == token1)
+                                || (fill.token_in == token1 && fill.token_out == token0)
+                        })
+                        .collect::<Vec<_>>();
+                    let Some(price_1over0) = fills.first().map(|fill| fill.price_1over0) else {
+                        continue;
+                    };
+
+                    let (amount0, amount1) =
+                        fills
+                            .iter()
+                            .fold((U256::ZERO, U256::ZERO), |(amount0, amount1), fill| {
+                                if fill.zero_for_one {
+                                    (amount0 + fill.amount_in, amount1 + fill.amount_out)
+                                } else {
+                                    (amount0 + fill.amount_out, amount1 + fill.amount_in)
+                                }
+                            });
+
+                    builder.add_bundle(
+                        block_number,
+                        timestamp(block_number),
+                        price_1over0,
+                        amount0,
+                        amount1
+                    );
+                }
+                Trade::Swap(swap) => {
+                    builder.add_swap(&swap, block_number, timestamp(block_number));
+                }
+            }
+        }
+
+        Ok(builder.finish())
+    }
+
+    /// The realized LP fee APR of every pool over the `window_blocks` blocks
+    /// up to `block_id`, highest first. LP fees are the Angstrom bundle
+    /// rewards plus the Uniswap fees of unlocked swaps.
+    async fn pool_aprs(
+        &self,
+        window_blocks: u64,
+        block_id: BlockId,
+        chain: AngstromL1Chain
+    ) -> eyre::Result<Vec<PoolApr>> {
+        let end_block = self.block_number_from_block_id(block_id).await?;
+        let start_block = end_block.saturating_sub(window_blocks);
+
+        let (buckets, pool_keys, timestamps) = tokio::try_join!(
+            self.pool_analytics(
+                Some(start_block),
+                Some(end_block),
+                BucketInterval::Blocks(window_blocks.max(1)),
+                None,
+                chain
+            ),
+            self.all_pool_keys(end_block.into(), chain),
+            self.block_timestamps([start_block, end_block].into_iter(), None)
+        )?;
+        let window_seconds = timestamps[&end_block].saturating_sub(timestamps[&start_block]);
+
+        let mut aprs = futures::future::try_join_all(pool_keys.into_iter().map(async |key| {
+            let pool_id = PoolId::from(key.pool_key);
+            let tvl = self.pool_tvl(pool_id, end_block.into(), chain).await?;
+
+            let (mut lp_fees, mut protocol_fees) = (0.0, 0.0);
+            for bucket in buckets.iter().filter(|bucket| bucket.pool_id == pool_id) {
+                lp_fees += tvl.value_in_token1(bucket.lp_fees.token0, bucket.lp_fees.token1);
+                protocol_fees +=
+                    tvl.value_in_token1(bucket.protocol_fees.token0, bucket.protocol_fees.token1);
+            }
+
+            Ok::<_, eyre::ErrReport>(PoolApr::new(
+                pool_id,
+                tvl,
+                window_seconds,
+                lp_fees,
+                0.0,
+                protocol_fees
+            ))
+        }))
+        .await?;
+        rank_by_apr(&mut aprs);
+
+        Ok(aprs)
+    }
+
+    /// The timestamps of `blocks`, keyed by block number.
+    async fn block_timestamps(
+        &self,
+        blocks: impl Iterator<Item = u64> + Send,
+        block_stream_buffer: Option<usize>
+    ) -> eyre::Result<HashMap<u64, u64>> {
+        let blocks = blocks.unique().collect::<Vec<_>>();
+
+        let mut block_stream = futures::stream::iter(blocks)
+            .map(async |block_number| {
+                let block = self
+                    .fetch_block_primitive(block_number.into(), false)
+                    .await?;
+                Ok::<_, eyre::ErrReport>((block_number, block.header().timestamp()))
+            })
+            .buffer_unordered(block_stream_buffer.unwrap_or(100));
+
+        let mut timestamps = HashMap::new();
+        while let Some(val) = block_stream.next().await {
+            let (block_number, timestamp) = val?;
+            timestamps.insert(block_number, timestamp);
+        }
+
+        Ok(timestamps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::l1::test_utils::valid_test_params::init_valid_position_params_with_provider;
+
+    #[tokio::test]
+    async fn test_pool_analytics() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+
+        let buckets = provider
+            .pool_analytics(
+                Some(state.valid_block_after_swaps - 100),
+                Some(state.valid_block_after_swaps),
+                BucketInterval::Blocks(50),
+                None,
+                AngstromL1Chain::Mainnet
+            )
+            .await
+            .unwrap();
+
+        assert!(!buckets.is_empty());
+        assert!(buckets.iter().any(|bucket| bucket.bundles > 0));
+        assert!(buckets.iter().all(|bucket| bucket.bucket_start % 50 == 0
+            && bucket.bucket_start >= state.valid_block_after_swaps - 150));
+    }
+
+    #[tokio::test]
+    async fn test_candles() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+
+        let candles = provider
+            .candles(
+                state.pool_id,
+                BucketInterval::Seconds(3600),
+                state.valid_block_after_swaps - 1000,
+                state.valid_block_after_swaps,
+                AngstromL1Chain::Mainnet
+            )
+            .await
+            .unwrap();
+
+        assert!(!candles.is_empty());
+        assert!(candles.iter().all(|candle| candle.start % 3600 == 0
+            && candle.low <= candle.open
+            && candle.high >= candle.close
+            && candle.trades > 0));
+    }
+
+    #[tokio::test]
+    async fn test_pool_aprs() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+
+        let aprs = provider
+            .pool_aprs(7200, state.valid_block_after_swaps.into(), AngstromL1Chain::Mainnet)
+            .await
+            .unwrap();
+
+        assert!(!aprs.is_empty());
+        assert!(
+            aprs.iter()
+                .all(|apr| apr.window_seconds > 0 && apr.apr >= 0.0)
+        );
+        assert!(aprs.windows(2).all(|pair| pair[0].apr >= pair[1].apr));
+    }
+}
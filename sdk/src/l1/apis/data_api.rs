@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     ops::Deref
 };
 
@@ -42,6 +42,7 @@ use crate::{
     l1::{AngstromL1Chain, types::*},
     types::{
         common::*,
+        pool_stats::{self, PoolTvl},
         pool_tick_loaders::{DEFAULT_TICKS_PER_BATCH, FullTickLoader, PoolTickDataLoader},
         utils::{
             historical_pool_manager_modify_liquidity_filter, historical_pool_manager_swap_filter
@@ -483,6 +484,98 @@ pub trait AngstromL1DataApi:
             }))
     }
 
+    /// The first successful Angstrom bundle in the block, with every order
+    /// resolved to its fill.
+    async fn decoded_bundle_by_block(
+        &self,
+        block_id: BlockId,
+        chain: AngstromL1Chain
+    ) -> eyre::Result<Option<WithEthMeta<DecodedBundle>>> {
+        let Some(bundle) = self.get_bundle_by_block(block_id, true, chain).await? else {
+            return Ok(None);
+        };
+
+        let block_number = bundle
+            .block_number
+            .ok_or_else(|| eyre::eyre!("bundle is not mined: {:?}", bundle.tx_hash))?;
+
+        let decoded = DecodedBundle::new(&bundle.inner, block_number)?;
+        Ok(Some(WithEthMeta::new(
+            bundle.block_number,
+            bundle.tx_hash,
+            bundle.tx_idx,
+            bundle.from,
+            decoded
+        )))
+    }
+
+    /// The reward updates of the pool in every bundle between `start_block`
+    /// and `end_block`, resolved to tick ranges with the pool's initialized
+    /// ticks at the parent block of each bundle.
+    async fn reward_history(
+        &self,
+        pool_id: PoolId,
+        start_block: u64,
+        end_block: u64,
+        chain: AngstromL1Chain
+    ) -> eyre::Result<Vec<PoolRewardDistribution>> {
+        let (pool_key, bundles) = tokio::try_join!(
+            self.pool_key_by_pool_id(pool_id, end_block.into(), chain),
+            self.historical_bundles(Some(start_block), Some(end_block), None, chain)
+        )?;
+        let (token0, token1) = (pool_key.pool_key.currency0, pool_key.pool_key.currency1);
+        let tick_spacing = pool_key.pool_key.tickSpacing.as_i32();
+
+        let mut updates = Vec::new();
+        for bundle in bundles {
+            let Some(block_number) = bundle.block_number else { continue };
+            let update = DecodedBundle::new(&bundle.inner, block_number)?
+                .pool_updates
+                .into_iter()
+                .find(|update| update.token0 == token0 && update.token1 == token1);
+            if let Some(update) = update {
+                updates.push((block_number, bundle.tx_hash, update.rewards));
+            }
+        }
+
+        let mut distributions = futures::future::try_join_all(updates.into_iter().map(
+            async |(block_number, tx_hash, rewards)| {
+                let parent_block = BlockId::from(block_number - 1);
+                let slot0 = self.slot0_by_pool_id(pool_id, parent_block, chain).await?;
+                let current_tick = slot0.tick.as_i32();
+
+                let (ticks, _) = self
+                    .load_tick_data_in_band(
+                        pool_id,
+                        current_tick,
+                        tick_spacing,
+                        parent_block,
+                        INITIAL_TICKS_PER_SIDE,
+                        DEFAULT_TICKS_PER_BATCH,
+                        chain.constants().uniswap_constants().pool_manager()
+                    )
+                    .await?;
+                let ticks = ticks
+                    .into_iter()
+                    .filter(|(_, info)| info.initialized)
+                    .map(|(tick, info)| (tick, info.liquidity_net))
+                    .collect::<BTreeMap<_, _>>();
+
+                Ok::<_, eyre::ErrReport>(PoolRewardDistribution {
+                    pool_id,
+                    block_number,
+                    tx_hash,
+                    current_tick,
+                    ranges: distribute_rewards(&rewards, current_tick, &ticks)
+                })
+            }
+        ))
+        .await?;
+        distributions.sort_by_key(|distribution| distribution.block_number);
+
+        Ok(distributions)
+    }
+
     async fn pool_data_by_tokens(
         &self,
         token0: Address,
@@ -612,6 +705,34 @@ pub trait AngstromL1DataApi:
         Ok(pools)
     }
 
+    /// The tokens locked in the pool's liquidity, from the ticks within
+    /// [`INITIAL_TICKS_PER_SIDE`] of the current tick.
+    async fn pool_tvl(
+        &self,
+        pool_id: PoolId,
+        block_id: BlockId,
+        chain: AngstromL1Chain
+    ) -> eyre::Result<PoolTvl> {
+        let pool_key = self.pool_key_by_pool_id(pool_id, block_id, chain).await?;
+
+        let uni_pool_key = UniPoolKey {
+            currency0:   pool_key.pool_key.currency0,
+            currency1:   pool_key.pool_key.currency1,
+            fee:         pool_key.pool_fee_in_e6,
+            tickSpacing: pool_key.pool_key.tickSpacing,
+            hooks:       pool_key.pool_key.hooks
+        };
+
+        pool_stats::pool_tvl(
+            self,
+            pool_id,
+            uni_pool_key,
+            chain.constants().uniswap_constants().pool_manager(),
+            block_id
+        )
+        .await
+    }
+
     async fn pool_config_store(
         &self,
         block_id: BlockId,
@@ -1014,6 +1135,24 @@ mod data_api_tests {
         assert!(!pool_data.pool.liquidity().initialized_ticks().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_pool_tvl() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+
+        let tvl = provider
+            .pool_tvl(
+                PoolId::from(state.pool_key),
+                state.block_number.into(),
+                AngstromL1Chain::Mainnet
+            )
+            .await
+            .unwrap();
+
+        assert!(tvl.token0 > 0.0);
+        assert!(tvl.token1 > 0.0);
+        assert!(tvl.total_in_token1() > tvl.token1);
+    }
+
     #[tokio::test]
     async fn test_all_pool_data() {
         let (provider, state) = init_valid_position_params_with_provider().await;
@@ -1087,6 +1226,49 @@ mod data_api_tests {
         assert!(bundle.is_some());
     }
 
+    #[tokio::test]
+    async fn test_decoded_bundle_by_block() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+
+        let bundle = provider
+            .decoded_bundle_by_block(state.valid_block_after_swaps.into(), AngstromL1Chain::Mainnet)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(bundle.inner.block_number, state.valid_block_after_swaps);
+        assert!(bundle.inner.all_fills().count() > 0);
+        assert!(
+            bundle
+                .inner
+                .all_fills()
+                .all(|fill| fill.signer.is_some() && fill.token_in != fill.token_out)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reward_history() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+
+        let history = provider
+            .reward_history(
+                PoolId::from(state.pool_key),
+                state.valid_block_after_swaps - 100,
+                state.valid_block_after_swaps,
+                AngstromL1Chain::Mainnet
+            )
+            .await
+            .unwrap();
+
+        assert!(!history.is_empty());
+        assert!(history.iter().all(|distribution| {
+            distribution
+                .ranges
+                .iter()
+                .all(|range| range.tick_lower < range.tick_upper && range.liquidity > 0)
+        }));
+    }
+
     #[tokio::test]
     async fn test_get_bundle_by_tx_hash() {
         let (provider, state) = init_valid_position_params_with_provider().await;
@@ -1,8 +1,16 @@
+pub(crate) mod analytics_api;
 pub(crate) mod data_api;
 pub(crate) mod node_api;
+pub(crate) mod on_chain;
 pub(crate) mod order_builder;
+#[cfg(feature = "local-reth")]
+pub(crate) mod replay_api;
 pub(crate) mod user_api;
+pub use analytics_api::AngstromL1AnalyticsApi;
 pub use data_api::AngstromL1DataApi;
-pub use node_api::{AngstromNodeApi, AngstromOrderApiClient};
+pub use node_api::{AngstromClient, AngstromNodeApi, AngstromOrderApiClient};
+pub use on_chain::{AngstromL1OnChain, AngstromL1OnChainApi};
 pub use order_builder::AngstromOrderBuilder;
+#[cfg(feature = "local-reth")]
+pub use replay_api::AngstromL1ReplayApi;
 pub use user_api::AngstromL1UserApi;
@@ -13,8 +13,15 @@ use angstrom_types_primitives::{
 };
 use auto_impl::auto_impl;
 use futures::{Stream, StreamExt, TryStreamExt};
+use jsonrpsee_core::{
+    ClientError,
+    client::{BatchResponse, ClientT, Subscription, SubscriptionClientT},
+    params::BatchRequestBuilder,
+    traits::ToRpcParams
+};
 use jsonrpsee_http_client::HttpClient;
 use jsonrpsee_ws_client::WsClient;
+use serde::de::DeserializeOwned;
 
 use crate::l1::types::errors::AngstromSdkError;
 
@@ -22,6 +29,109 @@ use crate::l1::types::errors::AngstromSdkError;
 pub trait AngstromOrderApiClient: OrderApiClient + MetricsApiClient + Send + Sync {}
 impl AngstromOrderApiClient for WsClient {}
 impl AngstromOrderApiClient for HttpClient {}
+impl AngstromOrderApiClient for AngstromClient {}
+
+/// A client of the angstrom node over a transport picked at runtime, such as
+/// from an [`AngstromConfig`](crate::l1::providers::AngstromConfig).
+/// Subscriptions need the ws transport.
+#[derive(Debug)]
+pub enum AngstromClient {
+    Http(HttpClient),
+    Ws(WsClient)
+}
+
+impl ClientT for AngstromClient {
+    fn notification<Params>(
+        &self,
+        method: &str,
+        params: Params
+    ) -> impl Future<Output = Result<(), ClientError>> + Send
+    where
+        Params: ToRpcParams + Send
+    {
+        async move {
+            match self {
+                AngstromClient::Http(client) => client.notification(method, params).await,
+                AngstromClient::Ws(client) => client.notification(method, params).await
+            }
+        }
+    }
+
+    fn request<R, Params>(
+        &self,
+        method: &str,
+        params: Params
+    ) -> impl Future<Output = Result<R, ClientError>> + Send
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send
+    {
+        async move {
+            match self {
+                AngstromClient::Http(client) => client.request(method, params).await,
+                AngstromClient::Ws(client) => client.request(method, params).await
+            }
+        }
+    }
+
+    fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>
+    ) -> impl Future<Output = Result<BatchResponse<'a, R>, ClientError>> + Send
+    where
+        R: DeserializeOwned + std::fmt::Debug + 'a
+    {
+        async move {
+            match self {
+                AngstromClient::Http(client) => client.batch_request(batch).await,
+                AngstromClient::Ws(client) => client.batch_request(batch).await
+            }
+        }
+    }
+}
+
+impl SubscriptionClientT for AngstromClient {
+    fn subscribe<'a, Notif, Params>(
+        &self,
+        subscribe_method: &'a str,
+        params: Params,
+        unsubscribe_method: &'a str
+    ) -> impl Future<Output = Result<Subscription<Notif>, ClientError>> + Send
+    where
+        Params: ToRpcParams + Send,
+        Notif: DeserializeOwned
+    {
+        async move {
+            match self {
+                AngstromClient::Http(client) => {
+                    client
+                        .subscribe(subscribe_method, params, unsubscribe_method)
+                        .await
+                }
+                AngstromClient::Ws(client) => {
+                    client
+                        .subscribe(subscribe_method, params, unsubscribe_method)
+                        .await
+                }
+            }
+        }
+    }
+
+    fn subscribe_to_method<Notif>(
+        &self,
+        method: &str
+    ) -> impl Future<Output = Result<Subscription<Notif>, ClientError>> + Send
+    where
+        Notif: DeserializeOwned
+    {
+        async move {
+            match self {
+                AngstromClient::Http(client) => client.subscribe_to_method(method).await,
+                AngstromClient::Ws(client) => client.subscribe_to_method(method).await
+            }
+        }
+    }
+}
 
 #[async_trait::async_trait]
 #[auto_impl(&, Box, Arc)]
@@ -0,0 +1,282 @@
+use alloy_eips::BlockId;
+use alloy_network::Ethereum;
+use alloy_primitives::{
+    Address, U256,
+    aliases::{I24, U24}
+};
+use alloy_provider::Provider;
+use angstrom_types_primitives::{
+    contract_bindings::pool_manager::PoolManager::{self, PoolKey},
+    contract_payloads::angstrom::{
+        AngstromBundle, AngstromPoolConfigStore, AngstromPoolPartialKey
+    },
+    primitive::PoolId
+};
+use uni_v4::L1FeeConfiguration;
+use uniswap_storage::v4::{UnpackedPositionInfo, UnpackedSlot0, V4UserLiquidityPosition};
+
+use super::{AngstromL1AnalyticsApi, AngstromL1DataApi, AngstromL1UserApi};
+use crate::{
+    l1::{AngstromL1Chain, types::*},
+    types::{
+        common::*,
+        fees::LiquidityPositionFees,
+        pool_stats::{PoolApr, PoolTvl}
+    }
+};
+
+impl<P> AngstromL1OnChainApi for P where P: AngstromL1DataApi {}
+
+/// Binds an api to one chain, so that its calls do not take a `chain`.
+#[async_trait::async_trait]
+pub trait AngstromL1OnChainApi: AngstromL1DataApi {
+    /// Binds the api to `chain`, failing if the eth provider is connected to
+    /// another chain.
+    async fn on_chain(&self, chain: AngstromL1Chain) -> eyre::Result<AngstromL1OnChain<'_, Self>> {
+        let chain_id = self.alloy_root_provider().await?.get_chain_id().await?;
+        if chain_id != chain.chain_id() {
+            return Err(eyre::eyre!(
+                "eth provider chain {chain_id} does not match angstrom chain {}",
+                chain.chain_id()
+            ));
+        }
+
+        Ok(AngstromL1OnChain { provider: self, chain })
+    }
+
+    /// Binds the api to the built-in chain that the eth provider is connected
+    /// to.
+    async fn on_detected_chain(&self) -> eyre::Result<AngstromL1OnChain<'_, Self>> {
+        let chain = AngstromL1Chain::from_provider(&self.alloy_root_provider().await?).await?;
+        Ok(AngstromL1OnChain { provider: self, chain })
+    }
+
+    /// Binds the api to `chain` without asking the eth provider for its chain
+    /// id, for providers that are not backed by a node.
+    fn on_chain_unchecked(&self, chain: AngstromL1Chain) -> AngstromL1OnChain<'_, Self> {
+        AngstromL1OnChain { provider: self, chain }
+    }
+}
+
+/// Generates the methods of [`AngstromL1OnChain`], which call the api method
+/// of the same name with the bound chain.
+macro_rules! on_chain_methods {
+    ($($api:ident::$name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {
+        $(
+            #[doc = concat!(
+                "[`", stringify!($api), "::", stringify!($name), "`] on the bound chain."
+            )]
+            pub async fn $name(&self, $($arg: $ty),*) -> eyre::Result<$ret> {
+                $api::$name(self.provider, $($arg,)* self.chain).await
+            }
+        )*
+    };
+}
+
+/// An api bound to a chain by [`AngstromL1OnChainApi`]. It has the methods of
+/// [`AngstromL1DataApi`], [`AngstromL1UserApi`] and [`AngstromL1AnalyticsApi`]
+/// that take a `chain`, without it.
+#[derive(Debug)]
+pub struct AngstromL1OnChain<'a, P> {
+    provider: &'a P,
+    chain:    AngstromL1Chain
+}
+
+impl<P> Clone for AngstromL1OnChain<'_, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for AngstromL1OnChain<'_, P> {}
+
+impl<'a, P> AngstromL1OnChain<'a, P> {
+    pub fn provider(&self) -> &'a P {
+        self.provider
+    }
+
+    pub fn chain(&self) -> AngstromL1Chain {
+        self.chain
+    }
+}
+
+impl<P: AngstromL1DataApi> AngstromL1OnChain<'_, P> {
+    on_chain_methods! {
+        AngstromL1DataApi::all_token_pairs(block_id: BlockId) -> Vec<TokenPair>;
+        AngstromL1DataApi::all_token_pairs_with_config_store(
+            config_store: AngstromPoolConfigStore,
+            block_id: BlockId
+        ) -> Vec<TokenPair>;
+        AngstromL1DataApi::all_tokens(block_id: BlockId) -> Vec<Address>;
+        AngstromL1DataApi::all_tokens_with_config_store(
+            config_store: AngstromPoolConfigStore,
+            block_id: BlockId
+        ) -> Vec<Address>;
+        AngstromL1DataApi::pool_key_by_tokens(
+            token0: Address,
+            token1: Address,
+            block_id: BlockId
+        ) -> PoolKeyWithAngstromFee;
+        AngstromL1DataApi::pool_key_by_pool_id(
+            pool_id: PoolId,
+            block_id: BlockId
+        ) -> PoolKeyWithAngstromFee;
+        AngstromL1DataApi::pool_key_by_pool_id_with_config_store(
+            pool_id: PoolId,
+            config_store: AngstromPoolConfigStore,
+            block_id: BlockId
+        ) -> PoolKeyWithAngstromFee;
+        AngstromL1DataApi::tokens_by_partial_pool_key(
+            partial_pool_key: AngstromPoolPartialKey,
+            block_id: BlockId
+        ) -> TokenPair;
+        AngstromL1DataApi::all_pool_keys(block_id: BlockId) -> Vec<PoolKeyWithAngstromFee>;
+        AngstromL1DataApi::all_pool_keys_with_config_store(
+            config_store: AngstromPoolConfigStore,
+            block_id: BlockId
+        ) -> Vec<PoolKeyWithAngstromFee>;
+        AngstromL1DataApi::pool_id(
+            token0: Address,
+            token1: Address,
+            block_id: BlockId
+        ) -> PoolId;
+        AngstromL1DataApi::historical_orders(
+            filter: HistoricalOrdersFilter,
+            block_stream_buffer: Option<usize>
+        ) -> Vec<WithEthMeta<Vec<HistoricalOrders>>>;
+        AngstromL1DataApi::historical_bundles(
+            start_block: Option<u64>,
+            end_block: Option<u64>,
+            block_stream_buffer: Option<usize>
+        ) -> Vec<WithEthMeta<AngstromBundle>>;
+        AngstromL1DataApi::historical_liquidity_changes(
+            start_block: Option<u64>,
+            end_block: Option<u64>
+        ) -> Vec<WithEthMeta<PoolManager::ModifyLiquidity>>;
+        AngstromL1DataApi::historical_post_bundle_unlock_swaps(
+            start_block: Option<u64>,
+            end_block: Option<u64>
+        ) -> Vec<WithEthMeta<PoolManager::Swap>>;
+        AngstromL1DataApi::get_bundle_by_block(
+            block_id: BlockId,
+            verify_successful_tx: bool
+        ) -> Option<WithEthMeta<AngstromBundle>>;
+        AngstromL1DataApi::decoded_bundle_by_block(
+            block_id: BlockId
+        ) -> Option<WithEthMeta<DecodedBundle>>;
+        AngstromL1DataApi::reward_history(
+            pool_id: PoolId,
+            start_block: u64,
+            end_block: u64
+        ) -> Vec<PoolRewardDistribution>;
+        AngstromL1DataApi::pool_data_by_tokens(
+            token0: Address,
+            token1: Address,
+            load_ticks: bool,
+            block_id: BlockId
+        ) -> (u64, BaselinePoolStateWithKey<Ethereum>);
+        AngstromL1DataApi::pool_data_by_pool_id(
+            pool_id: PoolId,
+            load_ticks: bool,
+            block_id: BlockId
+        ) -> (u64, BaselinePoolStateWithKey<Ethereum>);
+        AngstromL1DataApi::all_pool_data(
+            load_ticks: bool,
+            block_id: BlockId
+        ) -> Vec<(u64, BaselinePoolStateWithKey<Ethereum>)>;
+        AngstromL1DataApi::pool_tvl(pool_id: PoolId, block_id: BlockId) -> PoolTvl;
+        AngstromL1DataApi::pool_config_store(block_id: BlockId) -> AngstromPoolConfigStore;
+        AngstromL1DataApi::slot0_by_pool_id(pool_id: PoolId, block_id: BlockId) -> UnpackedSlot0;
+        AngstromL1DataApi::slot0_by_tokens(
+            token0: Address,
+            token1: Address,
+            block_id: BlockId
+        ) -> UnpackedSlot0;
+        AngstromL1DataApi::fee_configuration_by_pool_id(
+            pool_id: PoolId,
+            block_id: BlockId
+        ) -> L1FeeConfiguration;
+        AngstromL1DataApi::fee_configuration_by_tokens(
+            token0: Address,
+            token1: Address,
+            bundle_fee: Option<U24>,
+            block_id: BlockId
+        ) -> L1FeeConfiguration;
+
+        AngstromL1UserApi::position_and_pool_info(
+            position_token_id: U256,
+            block_id: BlockId
+        ) -> (PoolKey, UnpackedPositionInfo);
+        AngstromL1UserApi::position_liquidity(position_token_id: U256, block_id: BlockId) -> u128;
+        AngstromL1UserApi::all_user_positions(
+            owner: Address,
+            start_token_id: U256,
+            end_token_id: U256,
+            pool_id: Option<PoolId>,
+            max_results: Option<usize>,
+            block_id: BlockId
+        ) -> Vec<V4UserLiquidityPosition>;
+        AngstromL1UserApi::user_position_fees(
+            position_token_id: U256,
+            block_id: BlockId
+        ) -> LiquidityPositionFees;
+        AngstromL1UserApi::position_rewards(
+            position_token_id: U256,
+            from_block: u64,
+            to_block: u64
+        ) -> PositionRewards;
+        AngstromL1UserApi::angstrom_fees(
+            pool_id: PoolId,
+            current_pool_tick: I24,
+            position_token_id: U256,
+            tick_lower: I24,
+            tick_upper: I24,
+            block_id: BlockId
+        ) -> U256;
+
+        AngstromL1AnalyticsApi::pool_analytics(
+            start_block: Option<u64>,
+            end_block: Option<u64>,
+            interval: BucketInterval,
+            block_stream_buffer: Option<usize>
+        ) -> Vec<PoolAnalyticsBucket>;
+        AngstromL1AnalyticsApi::candles(
+            pool_id: PoolId,
+            interval: BucketInterval,
+            from_block: u64,
+            to_block: u64
+        ) -> Vec<Candle>;
+        AngstromL1AnalyticsApi::pool_aprs(window_blocks: u64, block_id: BlockId) -> Vec<PoolApr>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::l1::test_utils::valid_test_params::init_valid_position_params_with_provider;
+
+    #[tokio::test]
+    async fn test_on_chain() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+
+        let mainnet = provider.on_chain(AngstromL1Chain::Mainnet).await.unwrap();
+        let bound = mainnet
+            .pool_key_by_pool_id(state.pool_key.into(), state.block_number.into())
+            .await
+            .unwrap();
+        let unbound = provider
+            .pool_key_by_pool_id(
+                state.pool_key.into(),
+                state.block_number.into(),
+                AngstromL1Chain::Mainnet
+            )
+            .await
+            .unwrap();
+        assert_eq!(bound, unbound);
+
+        let detected = provider.on_detected_chain().await.unwrap();
+        assert_eq!(detected.chain(), AngstromL1Chain::Mainnet);
+
+        assert!(provider.on_chain(AngstromL1Chain::Sepolia).await.is_err());
+    }
+}
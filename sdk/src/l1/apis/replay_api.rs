@@ -0,0 +1,332 @@
+use std::{collections::HashMap, sync::Arc};
+
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_eips::BlockId;
+use alloy_network::{Ethereum, TransactionResponse};
+use alloy_primitives::{Address, B256, I256, TxHash, U256};
+use alloy_rpc_types::Header;
+use alloy_sol_types::SolCall;
+use angstrom_types_primitives::contract_bindings::angstrom::Angstrom;
+use revm::{
+    Context, DatabaseCommit, DatabaseRef, ExecuteEvm, MainBuilder,
+    context::{BlockEnv, TxEnv},
+    primitives::hardfork::SpecId,
+    state::{AccountInfo, Bytecode}
+};
+use revm_database::{CacheDB, DBErrorMarker, DatabaseAsyncRef, EmptyDBTyped, WrapDatabaseAsync};
+use tokio::runtime::Handle;
+use uniswap_storage::StorageSlotFetcher;
+
+use super::data_api::AngstromL1DataApi;
+use crate::{
+    l1::{
+        AngstromL1Chain,
+        types::{BundleReplay, StorageSlotDiff, add_balance_change}
+    },
+    types::providers::primitive_fetcher::PrimitivesFetcher
+};
+
+impl<P> AngstromL1ReplayApi for P where P: AngstromL1DataApi {}
+
+#[async_trait::async_trait]
+pub trait AngstromL1ReplayApi: AngstromL1DataApi {
+    /// Re-executes the Angstrom bundle transaction `tx_hash` of `block_number`
+    /// against state forked at the parent block, read through this provider.
+    /// The transactions before it in the block are replayed first; any that
+    /// revm rejects are skipped and listed in [`BundleReplay::skipped_txs`].
+    async fn replay_bundle(
+        &self,
+        block_number: u64,
+        tx_hash: TxHash,
+        chain: AngstromL1Chain
+    ) -> eyre::Result<BundleReplay>
+    where
+        Self: Clone + 'static
+    {
+        let parent_block = block_number
+            .checked_sub(1)
+            .ok_or_else(|| eyre::eyre!("the genesis block has no parent state to fork"))?;
+        let block = self
+            .fetch_block_primitive(block_number.into(), true)
+            .await?;
+        let chain_id = chain.chain_id();
+        let spec = spec_id(chain_id, block.header.number(), block.header.timestamp())?;
+        let angstrom_address = chain.constants().angstrom_address();
+
+        let mut txs = block.transactions.into_transactions().collect::<Vec<_>>();
+        let tx_idx = txs
+            .iter()
+            .position(|tx| tx.tx_hash() == tx_hash)
+            .ok_or_else(|| eyre::eyre!("tx {tx_hash:?} is not in block {block_number}"))?;
+
+        let bundle_tx = txs.remove(tx_idx);
+        if bundle_tx.to() != Some(angstrom_address)
+            || Angstrom::executeCall::abi_decode(bundle_tx.input()).is_err()
+        {
+            eyre::bail!("tx {tx_hash:?} is not an Angstrom bundle");
+        }
+        txs.truncate(tx_idx);
+
+        let block_env = block_env(&block.header);
+        let to_tx_env = |tx: &alloy_rpc_types::Transaction| TxEnv {
+            tx_type: tx.ty(),
+            caller: tx.from(),
+            gas_limit: tx.gas_limit(),
+            gas_price: tx.max_fee_per_gas(),
+            gas_priority_fee: tx.max_priority_fee_per_gas(),
+            kind: tx.kind(),
+            value: tx.value(),
+            data: tx.input().clone(),
+            nonce: tx.nonce(),
+            chain_id: tx.chain_id(),
+            access_list: tx.access_list().cloned().unwrap_or_default(),
+            blob_hashes: tx
+                .blob_versioned_hashes()
+                .map(|hashes| hashes.to_vec())
+                .unwrap_or_default(),
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas().unwrap_or_default(),
+            ..Default::default()
+        };
+        let prior_txs = txs
+            .iter()
+            .map(|tx| (tx.tx_hash(), to_tx_env(tx)))
+            .collect::<Vec<_>>();
+        let bundle_tx = to_tx_env(&bundle_tx);
+
+        let fork = PrimitivesDb { provider: self.clone(), block_id: parent_block.into() };
+        let handle = Handle::current();
+
+        tokio::task::spawn_blocking(move || {
+            let mut db = CacheDB::new(Arc::new(WrapDatabaseAsync::with_handle(fork, handle)));
+
+            let evm = |db: &CacheDB<_>| {
+                Context::<BlockEnv>::new(EmptyDBTyped::default(), spec)
+                    .with_ref_db(db)
+                    .modify_cfg_chained(|cfg| cfg.chain_id = chain_id)
+                    .with_block(block_env.clone())
+                    .build_mainnet()
+            };
+
+            let mut skipped_txs = Vec::new();
+            for (prior_tx_hash, tx) in prior_txs {
+                match evm(&db).transact(tx) {
+                    Ok(result) => db.commit(result.state),
+                    Err(_) => skipped_txs.push(prior_tx_hash)
+                }
+            }
+
+            let result = evm(&db)
+                .transact(bundle_tx)
+                .map_err(|e| eyre::eyre!("{e:?}"))?;
+
+            let mut replay = BundleReplay {
+                block_number,
+                tx_hash,
+                success: result.result.is_success(),
+                gas_used: result.result.gas_used(),
+                logs: result.result.logs().to_vec(),
+                storage_diff: HashMap::default(),
+                balance_changes: HashMap::default(),
+                skipped_txs
+            };
+
+            for (address, account) in result.state {
+                if !account.is_touched() {
+                    continue;
+                }
+
+                let balance_before = db
+                    .basic_ref(address)
+                    .map_err(|e| eyre::eyre!("{e:?}"))?
+                    .map(|info| info.balance)
+                    .unwrap_or_default();
+                add_balance_change(
+                    &mut replay.balance_changes,
+                    address,
+                    Address::ZERO,
+                    I256::from_raw(account.info.balance) - I256::from_raw(balance_before)
+                );
+
+                let slots = account
+                    .storage
+                    .into_iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(key, slot)| {
+                        (
+                            key,
+                            StorageSlotDiff {
+                                before: slot.original_value(),
+                                after:  slot.present_value()
+                            }
+                        )
+                    })
+                    .collect::<HashMap<U256, _>>();
+                if !slots.is_empty() {
+                    replay.storage_diff.insert(address, slots);
+                }
+            }
+
+            replay.add_erc20_transfers();
+
+            Ok(replay)
+        })
+        .await?
+    }
+}
+
+/// Reads the state at `block_id` through a [`PrimitivesFetcher`] for revm.
+#[derive(Debug)]
+struct PrimitivesDb<P> {
+    provider: P,
+    block_id: BlockId
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("{0:?}")]
+struct PrimitivesDbError(eyre::Report);
+
+impl DBErrorMarker for PrimitivesDbError {}
+
+impl<P> DatabaseAsyncRef for PrimitivesDb<P>
+where
+    P: PrimitivesFetcher<Ethereum> + StorageSlotFetcher
+{
+    type Error = PrimitivesDbError;
+
+    async fn basic_async_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self
+            .provider
+            .account_info_primitive(address, self.block_id)
+            .await
+            .map_err(PrimitivesDbError)?;
+        // an rpc node cannot tell an empty account from a missing one
+        if info.balance.is_zero() && info.nonce == 0 && info.code.is_empty() {
+            return Ok(None);
+        }
+        let code = Bytecode::new_raw(info.code);
+
+        Ok(Some(AccountInfo::new(info.balance, info.nonce, code.hash_slow(), code)))
+    }
+
+    /// Code is loaded along with its account.
+    async fn code_by_hash_async_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        Err(PrimitivesDbError(eyre::eyre!("code {code_hash:?} was not loaded with its account")))
+    }
+
+    async fn storage_async_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.provider
+            .storage_at(address, index.into(), self.block_id)
+            .await
+            .map_err(PrimitivesDbError)
+    }
+
+    async fn block_hash_async_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        let block = self
+            .provider
+            .fetch_block_primitive(number.into(), false)
+            .await
+            .map_err(PrimitivesDbError)?;
+
+        Ok(block.header.hash)
+    }
+}
+
+/// The hardfork of a mainnet or sepolia block. Forks up to the merge activate
+/// at a block number and later ones at a timestamp.
+fn spec_id(chain_id: u64, number: u64, timestamp: u64) -> eyre::Result<SpecId> {
+    let (merge_block, forks) = match chain_id {
+        1 => (
+            15_537_394,
+            [
+                (1_681_338_455, SpecId::SHANGHAI),
+                (1_710_338_135, SpecId::CANCUN),
+                (1_746_612_311, SpecId::PRAGUE),
+                (1_764_798_551, SpecId::OSAKA)
+            ]
+        ),
+        11_155_111 => (
+            1_735_371,
+            [
+                (1_677_557_088, SpecId::SHANGHAI),
+                (1_706_655_072, SpecId::CANCUN),
+                (1_741_159_776, SpecId::PRAGUE),
+                (1_760_427_360, SpecId::OSAKA)
+            ]
+        ),
+        _ => eyre::bail!("no hardfork schedule for chain {chain_id}")
+    };
+    if number < merge_block {
+        eyre::bail!("block {number} of chain {chain_id} is before the merge");
+    }
+
+    Ok(forks
+        .into_iter()
+        .rev()
+        .find(|(activation, _)| timestamp >= *activation)
+        .map_or(SpecId::MERGE, |(_, spec)| spec))
+}
+
+fn block_env(header: &Header) -> BlockEnv {
+    BlockEnv {
+        number: U256::from(header.number()),
+        beneficiary: header.beneficiary(),
+        timestamp: U256::from(header.timestamp()),
+        gas_limit: header.gas_limit(),
+        basefee: header.base_fee_per_gas().unwrap_or_default(),
+        difficulty: header.difficulty(),
+        prevrandao: header.mix_hash(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        l1::test_utils::valid_test_params::init_valid_position_params_with_provider,
+        types::providers::primitive_fetcher::PrimitivesFetcher
+    };
+
+    #[test]
+    fn test_spec_id() {
+        assert_eq!(spec_id(1, 15_537_394, 1_663_224_179).unwrap(), SpecId::MERGE);
+        assert_eq!(spec_id(1, 19_426_587, 1_710_338_135).unwrap(), SpecId::CANCUN);
+        assert_eq!(spec_id(1, 23_870_000, 1_763_910_000).unwrap(), SpecId::PRAGUE);
+        assert_eq!(spec_id(11_155_111, 9_000_000, 1_760_427_360).unwrap(), SpecId::OSAKA);
+        assert!(spec_id(1, 15_537_393, 1_663_224_167).is_err());
+        assert!(spec_id(31_337, 1, 1_763_910_000).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replay_bundle() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+
+        let block_number = provider
+            .tx_by_hash_primitive(state.bundle_tx_hash)
+            .await
+            .unwrap()
+            .unwrap()
+            .block_number
+            .unwrap();
+
+        let replay = provider
+            .replay_bundle(block_number, state.bundle_tx_hash, AngstromL1Chain::Mainnet)
+            .await
+            .unwrap();
+
+        assert!(replay.success);
+        assert!(replay.gas_used > 0);
+        assert!(replay.skipped_txs.is_empty());
+        assert!(
+            replay
+                .storage_diff
+                .contains_key(&AngstromL1Chain::Mainnet.constants().angstrom_address())
+        );
+        assert!(
+            replay
+                .balance_changes
+                .values()
+                .any(|tokens| tokens.keys().any(|token| *token != Address::ZERO))
+        );
+    }
+}
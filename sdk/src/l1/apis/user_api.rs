@@ -11,13 +11,14 @@ use uniswap_storage::{
         position_manager::{
             position_manager_next_token_id, position_manager_owner_of,
             position_manager_pool_key_and_info
-        }
+        },
+        utils::full_mul_x128
     }
 };
 
 use super::data_api::AngstromL1DataApi;
 use crate::{
-    l1::AngstromL1Chain,
+    l1::{AngstromL1Chain, types::PositionRewards},
     types::fees::{LiquidityPositionFees, uniswap_fee_deltas}
 };
 
@@ -211,6 +212,73 @@ pub trait AngstromL1UserApi: AngstromL1DataApi {
         ))
     }
 
+    /// The Angstrom rewards earned by the position between `from_block` and
+    /// `to_block`, from the growth of [`Self::angstrom_fees`], along with
+    /// its share of each bundle's rewards from [`Self::reward_history`].
+    ///
+    /// Assumes the position's liquidity did not change between the blocks.
+    async fn position_rewards(
+        &self,
+        position_token_id: U256,
+        from_block: u64,
+        to_block: u64,
+        chain: AngstromL1Chain
+    ) -> eyre::Result<PositionRewards> {
+        let ((pool_key, position_info), position_liquidity) = tokio::try_join!(
+            self.position_and_pool_info(position_token_id, to_block.into(), chain),
+            self.position_liquidity(position_token_id, to_block.into(), chain),
+        )?;
+        let pool_id = PoolId::from(pool_key);
+
+        let fees_at = async |block_number: u64| {
+            let slot0 = self
+                .slot0_by_pool_id(pool_id, block_number.into(), chain)
+                .await?;
+            self.angstrom_fees(
+                pool_id,
+                slot0.tick,
+                position_token_id,
+                position_info.tick_lower,
+                position_info.tick_upper,
+                block_number.into(),
+                chain
+            )
+            .await
+        };
+
+        let (fees_before, fees_after, history) = tokio::try_join!(
+            fees_at(from_block),
+            fees_at(to_block),
+            self.reward_history(pool_id, from_block + 1, to_block, chain)
+        )?;
+
+        let (tick_lower, tick_upper) =
+            (position_info.tick_lower.as_i32(), position_info.tick_upper.as_i32());
+        let bundles = history
+            .into_iter()
+            .map(|distribution| {
+                let share = distribution
+                    .ranges
+                    .iter()
+                    .map(|range| range.position_share(tick_lower, tick_upper, position_liquidity))
+                    .fold(U256::ZERO, |acc, share| acc + share);
+                (distribution.block_number, share)
+            })
+            .collect();
+
+        Ok(PositionRewards {
+            position_token_id,
+            from_block,
+            to_block,
+            // growth inside a range is modular and may wrap between the blocks
+            earned: full_mul_x128(
+                fees_after.wrapping_sub(fees_before),
+                U256::from(position_liquidity)
+            ),
+            bundles
+        })
+    }
+
     async fn angstrom_fees(
         &self,
         pool_id: PoolId,
@@ -365,4 +433,27 @@ mod user_api_tests {
         let expected = U256::from(90224992210989852552811100631246_u128);
         assert_eq!(results, expected);
     }
+
+    #[tokio::test]
+    async fn test_position_rewards() {
+        let (provider, pos_info) = init_valid_position_params_with_provider().await;
+        let from_block = pos_info.block_for_liquidity_add + 1;
+
+        let results = provider
+            .position_rewards(
+                pos_info.position_token_id,
+                from_block,
+                from_block + 100,
+                AngstromL1Chain::Mainnet
+            )
+            .await
+            .unwrap();
+
+        let bundle_total = results
+            .bundles
+            .iter()
+            .fold(U256::ZERO, |acc, (_, share)| acc + share);
+        assert!(results.earned > U256::ZERO);
+        assert!(results.earned.abs_diff(bundle_total) <= U256::from(results.bundles.len()));
+    }
 }
@@ -0,0 +1,375 @@
+//! Assembles an [`AngstromBundle`] from signed orders, for tests and local
+//! devnets.
+//!
+//! ```ignore
+//! let execute_call = BundleBuilder::new()
+//!     .with_pool(pool, price_1over0)
+//!     .with_order(tob_order)
+//!     .with_order(flash_order)
+//!     .build_execute_call()?;
+//! ```
+
+use alloy_primitives::{Address, B256, Bytes, U256};
+use angstrom_types_primitives::{
+    contract_bindings::angstrom::Angstrom,
+    contract_payloads::{
+        Asset, Pair, Signature,
+        angstrom::{
+            AngstromBundle, OrderQuantities, StandingValidation, TopOfBlockOrder, UserOrder
+        }
+    },
+    sol_bindings::{RawPoolOrder, grouped_orders::AllOrders}
+};
+use itertools::Itertools;
+use pade::PadeEncode;
+
+use crate::types::common::PoolMetadata;
+
+/// Builds a bundle where every order is fully filled at its pool's uniform
+/// clearing price.
+///
+/// The per-asset save/take/settle amounts, the pool updates and the gas used
+/// by each order are left at zero; set them on the built bundle if the test
+/// needs them.
+#[derive(Debug, Clone, Default)]
+pub struct BundleBuilder {
+    pools:  Vec<(PoolMetadata, U256)>,
+    orders: Vec<AllOrders>
+}
+
+impl BundleBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pool with its clearing price, token1 over token0 in ray.
+    pub fn with_pool(mut self, pool: PoolMetadata, price_1over0: U256) -> Self {
+        self.pools.push((pool, price_1over0));
+        self
+    }
+
+    /// Adds a signed order. Its pool must be added with [`Self::with_pool`].
+    pub fn with_order(mut self, order: AllOrders) -> Self {
+        self.orders.push(order);
+        self
+    }
+
+    pub fn with_orders(mut self, orders: impl IntoIterator<Item = AllOrders>) -> Self {
+        self.orders.extend(orders);
+        self
+    }
+
+    pub fn build(self) -> eyre::Result<AngstromBundle> {
+        let assets = self
+            .pools
+            .iter()
+            .flat_map(|(pool, _)| [pool.token0, pool.token1])
+            .sorted()
+            .dedup()
+            .map(|addr| Asset { addr, save: 0, take: 0, settle: 0 })
+            .collect::<Vec<_>>();
+        let asset_index =
+            |token: Address| assets.iter().position(|asset| asset.addr == token).unwrap() as u16;
+
+        let pools = self
+            .pools
+            .iter()
+            .sorted_by_key(|(pool, _)| (pool.token0, pool.token1))
+            .collect::<Vec<_>>();
+        if pools
+            .iter()
+            .tuple_windows()
+            .any(|((a, _), (b, _))| (a.token0, a.token1) == (b.token0, b.token1))
+        {
+            eyre::bail!("a pool was added more than once");
+        }
+
+        let pairs = pools
+            .iter()
+            .map(|(pool, price_1over0)| Pair {
+                index0:       asset_index(pool.token0),
+                index1:       asset_index(pool.token1),
+                store_index:  pool.storage_idx as u16,
+                price_1over0: *price_1over0
+            })
+            .collect::<Vec<_>>();
+        let pair_index = |token_in: Address, token_out: Address| {
+            let (token0, token1) =
+                if token_in < token_out { (token_in, token_out) } else { (token_out, token_in) };
+            pools
+                .iter()
+                .position(|(pool, _)| pool.token0 == token0 && pool.token1 == token1)
+                .map(|idx| idx as u16)
+                .ok_or_else(|| eyre::eyre!("no pool added for {token0:?} - {token1:?}"))
+        };
+
+        let mut top_of_block_orders = Vec::new();
+        let mut user_orders = Vec::new();
+        for order in &self.orders {
+            let token_in = order.token_in();
+            let token_out = order.token_out();
+            let pair_index = pair_index(token_in, token_out)?;
+            let zero_for_one = token_in < token_out;
+            let signature = order_signature(order)?;
+
+            match order {
+                AllOrders::TOB(tob) => top_of_block_orders.push(TopOfBlockOrder {
+                    use_internal: tob.use_internal,
+                    quantity_in: tob.quantity_in,
+                    quantity_out: tob.quantity_out,
+                    max_gas_asset_0: tob.max_gas_asset0,
+                    gas_used_asset_0: 0,
+                    pairs_index: pair_index,
+                    zero_for_1: zero_for_one,
+                    recipient: recipient(tob.recipient),
+                    signature
+                }),
+                AllOrders::ExactFlash(flash) => user_orders.push(UserOrder {
+                    ref_id: flash.ref_id,
+                    use_internal: flash.use_internal,
+                    pair_index,
+                    min_price: flash.min_price,
+                    recipient: recipient(flash.recipient),
+                    hook_data: hook_data(&flash.hook_data),
+                    zero_for_one,
+                    standing_validation: None,
+                    order_quantities: OrderQuantities::Exact { quantity: flash.amount },
+                    max_extra_fee_asset0: flash.max_extra_fee_asset0,
+                    extra_fee_asset0: 0,
+                    exact_in: flash.exact_in,
+                    signature
+                }),
+                AllOrders::PartialFlash(flash) => user_orders.push(UserOrder {
+                    ref_id: flash.ref_id,
+                    use_internal: flash.use_internal,
+                    pair_index,
+                    min_price: flash.min_price,
+                    recipient: recipient(flash.recipient),
+                    hook_data: hook_data(&flash.hook_data),
+                    zero_for_one,
+                    standing_validation: None,
+                    order_quantities: OrderQuantities::Partial {
+                        min_quantity_in: flash.min_amount_in,
+                        max_quantity_in: flash.max_amount_in,
+                        filled_quantity: flash.max_amount_in
+                    },
+                    max_extra_fee_asset0: flash.max_extra_fee_asset0,
+                    extra_fee_asset0: 0,
+                    exact_in: true,
+                    signature
+                }),
+                AllOrders::ExactStanding(standing) => user_orders.push(UserOrder {
+                    ref_id: standing.ref_id,
+                    use_internal: standing.use_internal,
+                    pair_index,
+                    min_price: standing.min_price,
+                    recipient: recipient(standing.recipient),
+                    hook_data: hook_data(&standing.hook_data),
+                    zero_for_one,
+                    standing_validation: Some(StandingValidation::new(
+                        standing.nonce,
+                        standing.deadline.to()
+                    )),
+                    order_quantities: OrderQuantities::Exact { quantity: standing.amount },
+                    max_extra_fee_asset0: standing.max_extra_fee_asset0,
+                    extra_fee_asset0: 0,
+                    exact_in: standing.exact_in,
+                    signature
+                }),
+                AllOrders::PartialStanding(standing) => user_orders.push(UserOrder {
+                    ref_id: standing.ref_id,
+                    use_internal: standing.use_internal,
+                    pair_index,
+                    min_price: standing.min_price,
+                    recipient: recipient(standing.recipient),
+                    hook_data: hook_data(&standing.hook_data),
+                    zero_for_one,
+                    standing_validation: Some(StandingValidation::new(
+                        standing.nonce,
+                        standing.deadline.to()
+                    )),
+                    order_quantities: OrderQuantities::Partial {
+                        min_quantity_in: standing.min_amount_in,
+                        max_quantity_in: standing.max_amount_in,
+                        filled_quantity: standing.max_amount_in
+                    },
+                    max_extra_fee_asset0: standing.max_extra_fee_asset0,
+                    extra_fee_asset0: 0,
+                    exact_in: true,
+                    signature
+                })
+            }
+        }
+
+        Ok(AngstromBundle {
+            assets,
+            pairs,
+            pool_updates: Vec::new(),
+            top_of_block_orders,
+            user_orders
+        })
+    }
+
+    /// Builds the bundle and PADE-encodes it into `Angstrom::execute`
+    /// calldata.
+    pub fn build_execute_call(self) -> eyre::Result<Angstrom::executeCall> {
+        Ok(Angstrom::executeCall { encoded: self.build()?.pade_encode().into() })
+    }
+}
+
+fn hook_data(hook_data: &Bytes) -> Option<Bytes> {
+    (!hook_data.is_empty()).then(|| hook_data.clone())
+}
+
+/// An unset recipient is hashed as zero by the contract, so only a zero
+/// recipient is left out. An explicit recipient equal to the signer is kept to
+/// keep the order's hash.
+fn recipient(recipient: Address) -> Option<Address> {
+    (recipient != Address::ZERO).then_some(recipient)
+}
+
+fn order_signature(order: &AllOrders) -> eyre::Result<Signature> {
+    let meta = match order {
+        AllOrders::TOB(o) => &o.meta,
+        AllOrders::ExactFlash(o) => &o.meta,
+        AllOrders::PartialFlash(o) => &o.meta,
+        AllOrders::ExactStanding(o) => &o.meta,
+        AllOrders::PartialStanding(o) => &o.meta
+    };
+
+    if !meta.isEcdsa {
+        return Ok(Signature::Contract { from: meta.from, signature: meta.signature.clone() });
+    }
+
+    let sig = order
+        .order_signature()
+        .map_err(|e| eyre::eyre!("invalid order signature: {e:?}"))?;
+
+    Ok(Signature::Ecdsa { v: sig.v() as u8 + 27, r: B256::from(sig.r()), s: B256::from(sig.s()) })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{
+        address,
+        aliases::{I24, U24}
+    };
+    use alloy_sol_types::SolCall;
+    use angstrom_types_primitives::{
+        contract_bindings::pool_manager::PoolManager::PoolKey,
+        sol_bindings::rpc_orders::{
+            ExactFlashOrder, OrderMeta, TopOfBlockOrder as RpcTopOfBlockOrder
+        }
+    };
+    use pade::PadeDecode;
+
+    use super::*;
+
+    const TOKEN0: Address = address!("0x1111111111111111111111111111111111111111");
+    const TOKEN1: Address = address!("0x2222222222222222222222222222222222222222");
+    const TOKEN2: Address = address!("0x3333333333333333333333333333333333333333");
+    const USER: Address = address!("0x4444444444444444444444444444444444444444");
+
+    fn pool(token0: Address, token1: Address, storage_idx: u64) -> PoolMetadata {
+        let pool_key = PoolKey {
+            currency0:   token0,
+            currency1:   token1,
+            fee:         U24::ZERO,
+            tickSpacing: I24::unchecked_from(60),
+            hooks:       Address::ZERO
+        };
+
+        PoolMetadata {
+            pool_key,
+            pool_id: pool_key.into(),
+            token0,
+            token1,
+            fee: 0,
+            tick_spacing: 60,
+            storage_idx
+        }
+    }
+
+    fn contract_meta() -> OrderMeta {
+        OrderMeta { isEcdsa: false, from: USER, signature: Bytes::from_static(&[1, 2, 3]) }
+    }
+
+    #[test]
+    fn test_bundle_builder_round_trip() {
+        let tob = RpcTopOfBlockOrder {
+            quantity_in: 100,
+            quantity_out: 200,
+            max_gas_asset0: 10,
+            asset_in: TOKEN1,
+            asset_out: TOKEN0,
+            recipient: USER,
+            meta: contract_meta(),
+            ..Default::default()
+        };
+        let flash = ExactFlashOrder {
+            exact_in: true,
+            amount: 1000,
+            asset_in: TOKEN1,
+            asset_out: TOKEN2,
+            recipient: TOKEN0,
+            meta: contract_meta(),
+            ..Default::default()
+        };
+
+        let execute_call = BundleBuilder::new()
+            .with_pool(pool(TOKEN1, TOKEN2, 1), U256::from(2))
+            .with_pool(pool(TOKEN0, TOKEN1, 0), U256::from(1))
+            .with_order(AllOrders::TOB(tob))
+            .with_order(AllOrders::ExactFlash(flash))
+            .build_execute_call()
+            .unwrap();
+
+        let call = Angstrom::executeCall::abi_decode(&execute_call.abi_encode()).unwrap();
+        let bundle = AngstromBundle::pade_decode(&mut call.encoded.as_ref(), None).unwrap();
+
+        assert_eq!(
+            bundle
+                .assets
+                .iter()
+                .map(|asset| asset.addr)
+                .collect::<Vec<_>>(),
+            vec![TOKEN0, TOKEN1, TOKEN2]
+        );
+        assert_eq!(
+            bundle
+                .pairs
+                .iter()
+                .map(|pair| (pair.index0, pair.index1, pair.store_index))
+                .collect::<Vec<_>>(),
+            vec![(0, 1, 0), (1, 2, 1)]
+        );
+
+        let tob = &bundle.top_of_block_orders[0];
+        assert_eq!((tob.pairs_index, tob.zero_for_1, tob.recipient), (0, false, Some(USER)));
+
+        let user = &bundle.user_orders[0];
+        assert_eq!((user.pair_index, user.zero_for_one, user.recipient), (1, true, Some(TOKEN0)));
+        assert_eq!(
+            user.signature,
+            Signature::Contract { from: USER, signature: Bytes::from_static(&[1, 2, 3]) }
+        );
+    }
+
+    #[test]
+    fn test_bundle_builder_missing_pool() {
+        let flash = ExactFlashOrder {
+            asset_in: TOKEN0,
+            asset_out: TOKEN2,
+            meta: contract_meta(),
+            ..Default::default()
+        };
+
+        assert!(
+            BundleBuilder::new()
+                .with_pool(pool(TOKEN0, TOKEN1, 0), U256::from(1))
+                .with_order(AllOrders::ExactFlash(flash))
+                .build()
+                .is_err()
+        );
+    }
+}
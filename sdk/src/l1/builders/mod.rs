@@ -1,2 +1,4 @@
+mod bundle_builder;
+pub use bundle_builder::*;
 mod position_manager_liquidity;
 pub use position_manager_liquidity::*;
@@ -9,6 +9,9 @@
 //! - Burning positions
 //! - Settlement and sweep operations
 //!
+//! It can also decode an encoded `PositionManagerLiquidity` (or the input of a
+//! `modifyLiquidities` transaction) back into typed [`LiquidityAction`]s.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -32,8 +35,9 @@
 use std::marker::PhantomData;
 
 pub use _liquidity_calls::PositionManagerLiquidity;
-use alloy_primitives::Bytes;
-use alloy_sol_types::SolCall;
+use alloy_primitives::{Bytes, U256};
+use alloy_sol_types::{SolCall, SolValue};
+use angstrom_types_primitives::contract_bindings::position_manager::PositionManager;
 
 impl PositionManagerLiquidity {
     /// Creates a new empty PositionManagerLiquidity instance
@@ -59,6 +63,117 @@ impl PositionManagerLiquidity {
             self.params.push(param.into());
         }
     }
+
+    /// Decodes the actions byte string and its params into typed
+    /// [`LiquidityAction`]s, in execution order
+    ///
+    /// Action bytes without a known decoder are returned as
+    /// [`LiquidityAction::Unknown`]
+    pub fn decode_actions(&self) -> Result<Vec<LiquidityAction>, alloy_sol_types::Error> {
+        if self.actions.len() != self.params.len() {
+            return Err(alloy_sol_types::Error::custom(format!(
+                "{} actions but {} params",
+                self.actions.len(),
+                self.params.len()
+            )));
+        }
+
+        self.actions
+            .iter()
+            .zip(&self.params)
+            .map(|(action, params)| LiquidityAction::decode(*action, params))
+            .collect()
+    }
+
+    /// Decodes the `unlockData` of a `modifyLiquidities` call
+    pub fn decode_unlock_data(unlock_data: &[u8]) -> Result<Self, alloy_sol_types::Error> {
+        <Self as SolValue>::abi_decode(unlock_data)
+            .or_else(|_| <Self as SolValue>::abi_decode_params(unlock_data))
+    }
+
+    /// Decodes the input of a `modifyLiquidities` transaction, returning the
+    /// liquidity payload and the deadline
+    pub fn decode_modify_liquidities(input: &[u8]) -> Result<(Self, U256), alloy_sol_types::Error> {
+        let call = PositionManager::modifyLiquiditiesCall::abi_decode(input)?;
+        Ok((Self::decode_unlock_data(&call.unlockData)?, call.deadline))
+    }
+}
+
+impl TryFrom<&PositionManager::modifyLiquiditiesCall> for PositionManagerLiquidity {
+    type Error = alloy_sol_types::Error;
+
+    fn try_from(value: &PositionManager::modifyLiquiditiesCall) -> Result<Self, Self::Error> {
+        Self::decode_unlock_data(&value.unlockData)
+    }
+}
+
+/// A single decoded PositionManager action with its parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiquidityAction {
+    IncreaseLiquidity(_liquidity_calls::increaseLiquidityCall),
+    DecreaseLiquidity(_liquidity_calls::decreaseLiquidityCall),
+    MintPosition(_liquidity_calls::mintPositionCall),
+    BurnPosition(_liquidity_calls::burnPositionCall),
+    SettlePair(_liquidity_calls::settlePairCall),
+    TakePair(_liquidity_calls::takePairCall),
+    Sweep(_liquidity_calls::sweepCall),
+    /// An action byte this decoder doesn't know about, with its raw params
+    Unknown {
+        action: u8,
+        params: Bytes
+    }
+}
+
+impl LiquidityAction {
+    /// Decodes the params of a single action
+    pub fn decode(action: u8, params: &[u8]) -> Result<Self, alloy_sol_types::Error> {
+        Ok(match action {
+            IncreaseLiquidity::ACTION_BYTE => {
+                Self::IncreaseLiquidity(decode_action_params::<IncreaseLiquidity>(params)?)
+            }
+            DescreaseLiquidity::ACTION_BYTE => {
+                Self::DecreaseLiquidity(decode_action_params::<DescreaseLiquidity>(params)?)
+            }
+            MintPosition::ACTION_BYTE => {
+                Self::MintPosition(decode_action_params::<MintPosition>(params)?)
+            }
+            BurnPosition::ACTION_BYTE => {
+                Self::BurnPosition(decode_action_params::<BurnPosition>(params)?)
+            }
+            SettlePair::ACTION_BYTE => {
+                Self::SettlePair(decode_action_params::<SettlePair>(params)?)
+            }
+            TakePair::ACTION_BYTE => Self::TakePair(decode_action_params::<TakePair>(params)?),
+            Sweep::ACTION_BYTE => Self::Sweep(decode_action_params::<Sweep>(params)?),
+            action => Self::Unknown { action, params: Bytes::copy_from_slice(params) }
+        })
+    }
+
+    /// The action byte identifier for this action
+    pub fn action_byte(&self) -> u8 {
+        match self {
+            Self::IncreaseLiquidity(_) => IncreaseLiquidity::ACTION_BYTE,
+            Self::DecreaseLiquidity(_) => DescreaseLiquidity::ACTION_BYTE,
+            Self::MintPosition(_) => MintPosition::ACTION_BYTE,
+            Self::BurnPosition(_) => BurnPosition::ACTION_BYTE,
+            Self::SettlePair(_) => SettlePair::ACTION_BYTE,
+            Self::TakePair(_) => TakePair::ACTION_BYTE,
+            Self::Sweep(_) => Sweep::ACTION_BYTE,
+            Self::Unknown { action, .. } => *action
+        }
+    }
+}
+
+/// Params built by this module carry the call selector, while params encoded
+/// by other tooling are the bare abi-encoded arguments, so both are accepted
+fn decode_action_params<A: HandleLiquidityAction>(
+    params: &[u8]
+) -> Result<A::Params, alloy_sol_types::Error> {
+    if params.starts_with(&A::Params::SELECTOR) {
+        A::Params::abi_decode(params)
+    } else {
+        A::Params::abi_decode_raw(params)
+    }
 }
 
 /// Trait for handling liquidity actions with associated parameters and action
@@ -313,6 +428,7 @@ pub mod _liquidity_calls {
             bytes[] params;
         }
 
+        #[derive(Debug, PartialEq, Eq)]
         struct PoolKey {
             address currency0;
             address currency1;
@@ -321,6 +437,7 @@ pub mod _liquidity_calls {
             address hooks;
         }
 
+        #[derive(Debug, PartialEq, Eq)]
         function increaseLiquidity(
             uint256 tokenId,
             uint256 liquidity,
@@ -329,6 +446,7 @@ pub mod _liquidity_calls {
             bytes calldata hookData
         );
 
+        #[derive(Debug, PartialEq, Eq)]
         function decreaseLiquidity(
             uint256 tokenId,
             uint256 liquidity,
@@ -336,6 +454,7 @@ pub mod _liquidity_calls {
             uint128 amount1Min,
             bytes calldata hookData
         );
+        #[derive(Debug, PartialEq, Eq)]
         function mintPosition(
             PoolKey calldata poolKey,
             int24 tickLower,
@@ -347,6 +466,7 @@ pub mod _liquidity_calls {
             bytes calldata hookData
         );
 
+        #[derive(Debug, PartialEq, Eq)]
         function burnPosition(
             uint256 tokenId,
             uint128 amount0Min,
@@ -354,17 +474,20 @@ pub mod _liquidity_calls {
             bytes calldata hookData
         );
 
+        #[derive(Debug, PartialEq, Eq)]
         function takePair(
             address currency0,
             address currency1,
             address recipient,
         );
 
+        #[derive(Debug, PartialEq, Eq)]
         function settlePair(
             address currency0,
             address currency1
         );
 
+        #[derive(Debug, PartialEq, Eq)]
         function sweep(
             address currency,
             address recipient,
@@ -768,4 +891,114 @@ mod tests {
         assert_eq!(converted_key.tickSpacing, pm_key.tickSpacing);
         assert_eq!(converted_key.hooks, pm_key.hooks);
     }
+
+    #[test]
+    fn test_decode_actions_round_trip() {
+        let mut liquidity_manager = PositionManagerLiquidity::new();
+
+        let pool_key = create_test_pool_key();
+        let mint_params = _liquidity_calls::mintPositionCall {
+            poolKey:    pool_key,
+            tickLower:  I24::try_from(-1000).unwrap(),
+            tickUpper:  I24::try_from(1000).unwrap(),
+            liquidity:  U256::from(100000),
+            amount0Max: 500000,
+            amount1Max: 500000,
+            owner:      address!("6666666666666666666666666666666666666666"),
+            hookData:   Bytes::default()
+        };
+        let settle_params = _liquidity_calls::settlePairCall {
+            currency0: pool_key.currency0,
+            currency1: pool_key.currency1
+        };
+
+        let mut builder = PositionManagerLiquidityBuilder::<MintPosition>::new(mint_params.clone())
+            .mint_position();
+        builder.add_settle(settle_params.clone());
+        liquidity_manager.chain_builder(builder);
+
+        let decoded = liquidity_manager.decode_actions().unwrap();
+
+        assert_eq!(
+            decoded,
+            vec![
+                LiquidityAction::MintPosition(mint_params),
+                LiquidityAction::SettlePair(settle_params)
+            ]
+        );
+        assert_eq!(decoded[0].action_byte(), MintPosition::ACTION_BYTE);
+    }
+
+    #[test]
+    fn test_decode_actions_without_selector() {
+        let take_params = _liquidity_calls::takePairCall {
+            currency0: address!("1111111111111111111111111111111111111111"),
+            currency1: address!("2222222222222222222222222222222222222222"),
+            recipient: address!("7777777777777777777777777777777777777777")
+        };
+
+        let liquidity_manager = PositionManagerLiquidity {
+            actions: Bytes::from_iter([TakePair::ACTION_BYTE, 0xff]),
+            params:  vec![take_params.abi_encode_raw().into(), Bytes::from_static(&[1, 2, 3])]
+        };
+
+        let decoded = liquidity_manager.decode_actions().unwrap();
+
+        assert_eq!(
+            decoded,
+            vec![
+                LiquidityAction::TakePair(take_params),
+                LiquidityAction::Unknown { action: 0xff, params: Bytes::from_static(&[1, 2, 3]) }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_actions_length_mismatch() {
+        let liquidity_manager = PositionManagerLiquidity {
+            actions: Bytes::from_iter([TakePair::ACTION_BYTE]),
+            params:  vec![]
+        };
+
+        assert!(liquidity_manager.decode_actions().is_err());
+    }
+
+    #[test]
+    fn test_decode_modify_liquidities() {
+        let mut liquidity_manager = PositionManagerLiquidity::new();
+
+        let burn_params = _liquidity_calls::burnPositionCall {
+            tokenId:    U256::from(999),
+            amount0Min: 10000,
+            amount1Min: 10000,
+            hookData:   Bytes::default()
+        };
+        let take_params = _liquidity_calls::takePairCall {
+            currency0: address!("1111111111111111111111111111111111111111"),
+            currency1: address!("2222222222222222222222222222222222222222"),
+            recipient: address!("7777777777777777777777777777777777777777")
+        };
+
+        let mut builder = PositionManagerLiquidityBuilder::<BurnPosition>::new(burn_params.clone())
+            .burn_position();
+        builder.add_take_pair(take_params.clone());
+        liquidity_manager.chain_builder(builder);
+
+        let deadline = U256::from(1234);
+        let input =
+            crate::l1::apis::AngstromOrderBuilder::modify_liquidities(liquidity_manager, deadline)
+                .abi_encode();
+
+        let (decoded, decoded_deadline) =
+            PositionManagerLiquidity::decode_modify_liquidities(&input).unwrap();
+
+        assert_eq!(decoded_deadline, deadline);
+        assert_eq!(
+            decoded.decode_actions().unwrap(),
+            vec![
+                LiquidityAction::BurnPosition(burn_params),
+                LiquidityAction::TakePair(take_params)
+            ]
+        );
+    }
 }
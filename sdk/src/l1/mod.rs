@@ -10,21 +10,91 @@ pub mod types;
 
 use std::fmt::Debug;
 
+use alloy_network::Network;
+use alloy_provider::Provider;
+use serde::{Deserialize, Serialize};
 use uniswap_storage::angstrom::mainnet::{
     ANGSTROM_L1_CONSTANTS_MAINNET, ANGSTROM_L1_CONSTANTS_SEPOLIA_TESTNET, AngstromL1Constants
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum AngstromL1Chain {
     Mainnet,
-    Sepolia
+    Sepolia,
+    /// A deployment that is not built in, such as a devnet or a local anvil.
+    /// It is not serializable.
+    #[serde(skip)]
+    Custom(AngstromL1Constants)
 }
 
 impl AngstromL1Chain {
     pub fn constants(&self) -> AngstromL1Constants {
         match self {
             AngstromL1Chain::Mainnet => ANGSTROM_L1_CONSTANTS_MAINNET,
-            AngstromL1Chain::Sepolia => ANGSTROM_L1_CONSTANTS_SEPOLIA_TESTNET
+            AngstromL1Chain::Sepolia => ANGSTROM_L1_CONSTANTS_SEPOLIA_TESTNET,
+            AngstromL1Chain::Custom(constants) => *constants
         }
     }
+
+    pub fn chain_id(&self) -> u64 {
+        self.constants().chain_id()
+    }
+
+    /// The built-in chain with the chain id, if any.
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        [AngstromL1Chain::Mainnet, AngstromL1Chain::Sepolia]
+            .into_iter()
+            .find(|chain| chain.chain_id() == chain_id)
+    }
+
+    /// The built-in chain that the provider is connected to.
+    pub async fn from_provider<N: Network>(provider: &impl Provider<N>) -> eyre::Result<Self> {
+        let chain_id = provider.get_chain_id().await?;
+        Self::from_chain_id(chain_id)
+            .ok_or_else(|| eyre::eyre!("no angstrom l1 deployment on chain {chain_id}"))
+    }
+}
+
+/// Custom deployments are equal if they are the same angstrom contract on the
+/// same chain.
+impl PartialEq for AngstromL1Chain {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (AngstromL1Chain::Mainnet, AngstromL1Chain::Mainnet)
+            | (AngstromL1Chain::Sepolia, AngstromL1Chain::Sepolia) => true,
+            (AngstromL1Chain::Custom(a), AngstromL1Chain::Custom(b)) => {
+                a.chain_id() == b.chain_id() && a.angstrom_address() == b.angstrom_address()
+            }
+            _ => false
+        }
+    }
+}
+
+impl Eq for AngstromL1Chain {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_chain() {
+        let custom = AngstromL1Chain::Custom(ANGSTROM_L1_CONSTANTS_MAINNET);
+
+        assert_eq!(custom.chain_id(), AngstromL1Chain::Mainnet.chain_id());
+        assert_eq!(
+            custom.constants().angstrom_address(),
+            AngstromL1Chain::Mainnet.constants().angstrom_address()
+        );
+        assert_eq!(custom, AngstromL1Chain::Custom(ANGSTROM_L1_CONSTANTS_MAINNET));
+        assert_ne!(custom, AngstromL1Chain::Custom(ANGSTROM_L1_CONSTANTS_SEPOLIA_TESTNET));
+        assert_ne!(custom, AngstromL1Chain::Mainnet);
+    }
+
+    #[test]
+    fn test_from_chain_id() {
+        assert_eq!(AngstromL1Chain::from_chain_id(1), Some(AngstromL1Chain::Mainnet));
+        assert_eq!(AngstromL1Chain::from_chain_id(11155111), Some(AngstromL1Chain::Sepolia));
+        assert_eq!(AngstromL1Chain::from_chain_id(8453), None);
+    }
 }
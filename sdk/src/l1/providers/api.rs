@@ -1,10 +1,10 @@
 use std::fmt::Debug;
 
 use alloy_eips::BlockId;
-use alloy_network::{Ethereum, Network, TxSigner};
-use alloy_primitives::{Address, FixedBytes, Signature, TxHash};
+use alloy_network::{Ethereum, Network, ReceiptResponse, TransactionBuilder, TxSigner};
+use alloy_primitives::{Address, Bytes, FixedBytes, Signature, TxHash};
 use alloy_provider::{Provider, RootProvider};
-use alloy_rpc_types::{Filter, Log};
+use alloy_rpc_types::{AccountInfo, Filter, Log, TransactionRequest};
 use alloy_signer::{Signer, SignerSync};
 use alloy_sol_types::{SolCall, SolType};
 use angstrom_types_primitives::sol_bindings::grouped_orders::AllOrders;
@@ -17,6 +17,7 @@ use crate::{
         apis::node_api::{AngstromNodeApi, AngstromOrderApiClient},
         providers::backend::AngstromProvider,
         types::{
+            LiquidityCall, LiquidityExecution,
             errors::AngstromSdkError,
             fillers::{
                 AngstromFillProvider, AngstromFiller, AngstromSignerFiller, FillWrapper,
@@ -93,6 +94,14 @@ where
         &self.provider
     }
 
+    /// Signs transactions sent through the eth provider with `signer`.
+    pub fn with_wallet<S>(self, signer: S) -> Self
+    where
+        S: Signer + SignerSync + TxSigner<Signature> + Send + Sync + 'static
+    {
+        Self { provider: self.provider.with_wallet(signer), filler: self.filler }
+    }
+
     pub fn with_filler<F1: AngstromFiller>(
         self,
         filler: F1
@@ -166,6 +175,100 @@ where
     pub fn from_address(&self) -> Option<Address> {
         self.filler.from()
     }
+
+    /// `eth_call`s a liquidity call from the wallet, decoding the revert
+    /// reason if it fails.
+    pub async fn simulate_liquidity(
+        &self,
+        call: impl Into<LiquidityCall>,
+        chain: AngstromL1Chain
+    ) -> Result<Bytes, AngstromSdkError> {
+        let tx = self.liquidity_tx(&call.into(), chain)?;
+
+        self.eth_provider()
+            .provider()
+            .call(tx)
+            .await
+            .map_err(AngstromSdkError::from_rpc_revert)
+    }
+
+    /// Sends a liquidity call from the wallet and waits for its receipt.
+    ///
+    /// If `dry_run` is set, the call is simulated first and nothing is sent
+    /// if it reverts.
+    pub async fn execute_liquidity(
+        &self,
+        call: impl Into<LiquidityCall>,
+        chain: AngstromL1Chain,
+        dry_run: bool
+    ) -> Result<LiquidityExecution, AngstromSdkError> {
+        let call = call.into();
+        let provider = self.eth_provider().provider();
+        let mut tx = self.liquidity_tx(&call, chain)?;
+
+        if dry_run {
+            provider
+                .call(tx.clone())
+                .await
+                .map_err(AngstromSdkError::from_rpc_revert)?;
+        }
+
+        let from = self
+            .provider
+            .wallet_address()
+            .ok_or(AngstromSdkError::MissingWallet)?;
+        let (gas_limit, nonce, chain_id, fees) = tokio::try_join!(
+            async {
+                provider
+                    .estimate_gas(tx.clone())
+                    .await
+                    .map_err(AngstromSdkError::from_rpc_revert)
+            },
+            async {
+                Ok::<_, AngstromSdkError>(provider.get_transaction_count(from).pending().await?)
+            },
+            async { Ok::<_, AngstromSdkError>(provider.get_chain_id().await?) },
+            async { Ok::<_, AngstromSdkError>(provider.estimate_eip1559_fees().await?) }
+        )?;
+
+        tx.set_gas_limit(gas_limit);
+        tx.set_nonce(nonce);
+        tx.set_chain_id(chain_id);
+        tx.set_max_fee_per_gas(fees.max_fee_per_gas);
+        tx.set_max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+
+        let receipt = provider.send_transaction(tx).await?.get_receipt().await?;
+        if !receipt.status() {
+            return Err(AngstromSdkError::TransactionFailed(receipt.transaction_hash));
+        }
+
+        let uniswap_constants = chain.constants().uniswap_constants();
+        Ok(LiquidityExecution::from_receipt_logs(
+            receipt.transaction_hash,
+            receipt.block_number,
+            receipt.gas_used,
+            uniswap_constants.pool_manager(),
+            uniswap_constants.position_manager(),
+            receipt.logs()
+        ))
+    }
+
+    fn liquidity_tx(
+        &self,
+        call: &LiquidityCall,
+        chain: AngstromL1Chain
+    ) -> Result<TransactionRequest, AngstromSdkError> {
+        let from = self
+            .provider
+            .wallet_address()
+            .ok_or(AngstromSdkError::MissingWallet)?;
+
+        Ok(TransactionRequest::default()
+            .with_from(from)
+            .with_to(call.target(chain))
+            .with_input(call.calldata())
+            .with_value(call.native_value()))
+    }
 }
 
 #[async_trait::async_trait]
@@ -237,6 +340,16 @@ where
         self.provider.view_deploy_call::<IC>(block_id, tx).await
     }
 
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<AccountInfo> {
+        self.provider
+            .account_info_primitive(address, block_id)
+            .await
+    }
+
     async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<Ethereum>> {
         self.provider.alloy_root_provider().await
     }
@@ -4,7 +4,7 @@ use alloy_eips::BlockId;
 use alloy_network::{Ethereum, EthereumWallet, Network, TxSigner};
 use alloy_primitives::{Address, Signature, TxHash};
 use alloy_provider::{Provider, RootProvider};
-use alloy_rpc_types::{Filter, Log};
+use alloy_rpc_types::{AccountInfo, Filter, Log};
 use alloy_signer::{Signer, SignerSync};
 use alloy_sol_types::{SolCall, SolType};
 use jsonrpsee_http_client::HttpClient;
@@ -21,7 +21,8 @@ where
     T: AngstromOrderApiClient
 {
     eth_provider:      AlloyProviderWrapper,
-    angstrom_provider: T
+    angstrom_provider: T,
+    wallet_address:    Option<Address>
 }
 
 impl AngstromProvider<HttpClient> {
@@ -31,7 +32,8 @@ impl AngstromProvider<HttpClient> {
     ) -> eyre::Result<Self> {
         Ok(Self {
             eth_provider:      AlloyProviderWrapper::new(eth_provider),
-            angstrom_provider: HttpClient::builder().build(angstrom_url)?
+            angstrom_provider: HttpClient::builder().build(angstrom_url)?,
+            wallet_address:    None
         })
     }
 }
@@ -43,14 +45,19 @@ impl AngstromProvider<WsClient> {
     ) -> eyre::Result<Self> {
         Ok(Self {
             eth_provider:      AlloyProviderWrapper::new(eth_provider),
-            angstrom_provider: WsClientBuilder::new().build(angstrom_url).await?
+            angstrom_provider: WsClientBuilder::new().build(angstrom_url).await?,
+            wallet_address:    None
         })
     }
 }
 
 impl<T: AngstromOrderApiClient> AngstromProvider<T> {
     pub fn new_with_providers(eth_provider: impl Provider + 'static, angstrom_provider: T) -> Self {
-        Self { eth_provider: AlloyProviderWrapper::new(eth_provider), angstrom_provider }
+        Self {
+            eth_provider: AlloyProviderWrapper::new(eth_provider),
+            angstrom_provider,
+            wallet_address: None
+        }
     }
 
     /// Returns the wrapped Ethereum provider.
@@ -59,17 +66,24 @@ impl<T: AngstromOrderApiClient> AngstromProvider<T> {
         &self.eth_provider
     }
 
+    /// The address of the wallet set with [`Self::with_wallet`].
+    pub fn wallet_address(&self) -> Option<Address> {
+        self.wallet_address
+    }
+
     pub fn with_wallet<S>(self, signer: S) -> AngstromProvider<T>
     where
         S: Signer + SignerSync + TxSigner<Signature> + Send + Sync + 'static
     {
+        let wallet_address = Signer::address(&signer);
         let eth_provider = alloy_provider::builder::<Ethereum>()
             .wallet(EthereumWallet::new(signer))
             .connect_provider(self.eth_provider.clone());
 
         AngstromProvider {
             eth_provider:      AlloyProviderWrapper::new(eth_provider),
-            angstrom_provider: self.angstrom_provider
+            angstrom_provider: self.angstrom_provider,
+            wallet_address:    Some(wallet_address)
         }
     }
 }
@@ -118,6 +132,16 @@ where
         self.eth_provider.view_deploy_call::<IC>(block_id, tx).await
     }
 
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<AccountInfo> {
+        self.eth_provider
+            .account_info_primitive(address, block_id)
+            .await
+    }
+
     async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<Ethereum>> {
         self.eth_provider.alloy_root_provider().await
     }
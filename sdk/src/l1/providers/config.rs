@@ -0,0 +1,427 @@
+use std::{collections::HashMap, path::PathBuf, str::FromStr, time::Duration};
+
+use alloy_network::Ethereum;
+use alloy_provider::{DynProvider, Provider, ProviderBuilder};
+use alloy_signer_local::PrivateKeySigner;
+use jsonrpsee_http_client::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    l1::{
+        AngstromApi, AngstromL1Chain,
+        apis::node_api::{AngstromClient, AngstromOrderApiClient},
+        providers::AngstromApiBuilder,
+        types::{
+            errors::AngstromSdkError,
+            fillers::{
+                AngstromFillProvider, AngstromSignerFiller, NonceGeneratorFiller,
+                TokenBalanceCheckFiller
+            }
+        }
+    },
+    types::providers::{AlloyProviderWrapper, MultiProviderWrapper}
+};
+
+/// The fillers of an [`AngstromApi`] built from an [`AngstromConfig`], each
+/// of which is `None` if it is switched off.
+pub type ConfiguredFillers = AngstromFillProvider<
+    AngstromFillProvider<
+        AngstromFillProvider<(), Option<NonceGeneratorFiller>>,
+        Option<TokenBalanceCheckFiller>
+    >,
+    Option<AngstromSignerFiller<PrivateKeySigner>>
+>;
+
+/// Everything needed to connect an [`AngstromApi`], loaded from a TOML or
+/// JSON file or from the environment.
+///
+/// ```toml
+/// chain = "mainnet"
+/// eth_rpc_urls = ["wss://eth.example.com", "https://eth-fallback.example.com"]
+/// angstrom_url = "https://angstrom.example.com"
+/// transport = "http"
+///
+/// [signer]
+/// private_key_env = "ANGSTROM_PRIVATE_KEY"
+///
+/// [fillers]
+/// balance_check = false
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AngstromConfig {
+    #[serde(default = "default_chain")]
+    pub chain:        AngstromL1Chain,
+    /// requests are routed over all of them, see
+    /// [`AngstromConfig::connect_eth_provider`]
+    pub eth_rpc_urls: Vec<String>,
+    pub angstrom_url: String,
+    /// how to connect to the angstrom node, see [`AngstromConfig::transport`]
+    #[serde(default)]
+    pub transport:    Option<AngstromTransport>,
+    #[serde(default)]
+    pub client:       AngstromClientConfig,
+    #[serde(default)]
+    pub signer:       Option<SignerConfig>,
+    #[serde(default)]
+    pub fillers:      FillerConfig
+}
+
+fn default_chain() -> AngstromL1Chain {
+    AngstromL1Chain::Mainnet
+}
+
+/// The transport of the client connected to the angstrom node.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AngstromTransport {
+    Http,
+    Ws
+}
+
+/// Options of the client connected to the angstrom node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct AngstromClientConfig {
+    pub request_timeout_secs:  Option<u64>,
+    /// sent with every request, such as `Authorization`
+    pub headers:               HashMap<String, String>,
+    pub max_request_size:      Option<u32>,
+    pub max_response_size:     Option<u32>,
+    /// ws only
+    pub ws_ping_interval_secs: Option<u64>
+}
+
+/// Where the wallet's key is read from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SignerConfig {
+    /// a hex private key in the environment variable
+    PrivateKeyEnv { private_key_env: String },
+    /// an encrypted keystore file, with its password in the environment
+    /// variable
+    Keystore { keystore_path: PathBuf, password_env: String }
+}
+
+/// Which fillers are applied to orders. The signer filler is only added if a
+/// signer is configured.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct FillerConfig {
+    pub nonce:         bool,
+    pub balance_check: bool,
+    pub signer:        bool
+}
+
+impl Default for FillerConfig {
+    fn default() -> Self {
+        Self { nonce: true, balance_check: true, signer: true }
+    }
+}
+
+impl AngstromConfig {
+    pub fn from_toml_str(toml: &str) -> Result<Self, AngstromSdkError> {
+        toml::from_str(toml).map_err(|e| AngstromSdkError::Config(e.to_string()))
+    }
+
+    pub fn from_json_str(json: &str) -> Result<Self, AngstromSdkError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Reads a `.json` file as JSON and any other file as TOML.
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self, AngstromSdkError> {
+        let path = path.into();
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| AngstromSdkError::Config(format!("could not read {path:?}: {e}")))?;
+
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json_str(&contents)
+        } else {
+            Self::from_toml_str(&contents)
+        }
+    }
+
+    /// Reads the config from the environment and any `.env` file:
+    ///
+    /// - `ANGSTROM_CHAIN`: `mainnet` (default) or `sepolia`
+    /// - `ETH_RPC_URLS`: comma separated, falling back to `ETH_WS_URL`
+    /// - `ANGSTROM_URL`, falling back to `ANGSTROM_HTTP_URL`
+    /// - `ANGSTROM_TRANSPORT`: `http` or `ws`, see
+    ///   [`AngstromConfig::transport`]
+    /// - `ANGSTROM_KEYSTORE` and `ANGSTROM_KEYSTORE_PASSWORD`, or
+    ///   `ANGSTROM_PRIVATE_KEY`: the signer, if any
+    /// - `ANGSTROM_FILLERS`: comma separated fillers to enable, out of `nonce`,
+    ///   `balance_check` and `signer`. Defaults to all of them.
+    pub fn from_env() -> Result<Self, AngstromSdkError> {
+        dotenv::dotenv().ok();
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let missing = |name: &str| AngstromSdkError::Config(format!("{name} is not set"));
+
+        let chain = match var("ANGSTROM_CHAIN").as_deref() {
+            None => default_chain(),
+            Some(chain) => serde_json::from_value(chain.to_lowercase().into())?
+        };
+
+        let eth_rpc_urls = var("ETH_RPC_URLS")
+            .or_else(|| var("ETH_WS_URL"))
+            .ok_or_else(|| missing("ETH_RPC_URLS"))?
+            .split(',')
+            .map(|url| url.trim().to_owned())
+            .filter(|url| !url.is_empty())
+            .collect();
+
+        let angstrom_url = var("ANGSTROM_URL")
+            .or_else(|| var("ANGSTROM_HTTP_URL"))
+            .ok_or_else(|| missing("ANGSTROM_URL"))?;
+
+        let transport = var("ANGSTROM_TRANSPORT")
+            .map(|transport| serde_json::from_value(transport.to_lowercase().into()))
+            .transpose()?;
+
+        let signer = if let Some(keystore_path) = var("ANGSTROM_KEYSTORE") {
+            Some(SignerConfig::Keystore {
+                keystore_path: keystore_path.into(),
+                password_env:  "ANGSTROM_KEYSTORE_PASSWORD".to_owned()
+            })
+        } else {
+            var("ANGSTROM_PRIVATE_KEY").map(|_| SignerConfig::PrivateKeyEnv {
+                private_key_env: "ANGSTROM_PRIVATE_KEY".to_owned()
+            })
+        };
+
+        let fillers = match var("ANGSTROM_FILLERS") {
+            None => FillerConfig::default(),
+            Some(fillers) => {
+                let enabled = fillers.split(',').map(str::trim).collect::<Vec<_>>();
+                if let Some(unknown) = enabled
+                    .iter()
+                    .find(|f| !["nonce", "balance_check", "signer"].contains(*f))
+                {
+                    return Err(AngstromSdkError::Config(format!("unknown filler: {unknown}")));
+                }
+
+                FillerConfig {
+                    nonce:         enabled.contains(&"nonce"),
+                    balance_check: enabled.contains(&"balance_check"),
+                    signer:        enabled.contains(&"signer")
+                }
+            }
+        };
+
+        Ok(Self {
+            chain,
+            eth_rpc_urls,
+            angstrom_url,
+            transport,
+            client: AngstromClientConfig::default(),
+            signer,
+            fillers
+        })
+    }
+
+    /// Loads the configured signer, if any.
+    pub fn load_signer(&self) -> Result<Option<PrivateKeySigner>, AngstromSdkError> {
+        let secret = |name: &str| {
+            std::env::var(name).map_err(|_| AngstromSdkError::Config(format!("{name} is not set")))
+        };
+
+        let signer = match &self.signer {
+            None => return Ok(None),
+            Some(SignerConfig::PrivateKeyEnv { private_key_env }) => {
+                PrivateKeySigner::from_str(secret(private_key_env)?.trim()).map_err(|e| {
+                    AngstromSdkError::Config(format!("invalid key in {private_key_env}: {e}"))
+                })?
+            }
+            Some(SignerConfig::Keystore { keystore_path, password_env }) => {
+                PrivateKeySigner::decrypt_keystore(keystore_path, secret(password_env)?).map_err(
+                    |e| {
+                        AngstromSdkError::Config(format!("invalid keystore {keystore_path:?}: {e}"))
+                    }
+                )?
+            }
+        };
+
+        Ok(Some(signer))
+    }
+
+    /// The configured transport, otherwise ws for `ws://` and `wss://` urls
+    /// and http for the rest.
+    pub fn transport(&self) -> AngstromTransport {
+        self.transport.unwrap_or_else(|| {
+            if self.angstrom_url.starts_with("ws") {
+                AngstromTransport::Ws
+            } else {
+                AngstromTransport::Http
+            }
+        })
+    }
+
+    /// Connects to every eth rpc url that answers and routes requests over
+    /// them with a [`MultiProviderWrapper`]. Fails if none answer or if one is
+    /// on another chain than the configured one.
+    pub async fn connect_eth_provider(&self) -> Result<DynProvider, AngstromSdkError> {
+        let mut providers = Vec::new();
+        let mut last_err = AngstromSdkError::Config("no eth rpc urls are configured".to_owned());
+
+        for url in &self.eth_rpc_urls {
+            let provider = match ProviderBuilder::<_, _, Ethereum>::default()
+                .connect(url)
+                .await
+            {
+                Ok(provider) => provider,
+                Err(e) => {
+                    last_err = e.into();
+                    continue;
+                }
+            };
+
+            match provider.get_chain_id().await {
+                Ok(chain_id) if chain_id == self.chain.chain_id() => {
+                    providers.push(AlloyProviderWrapper::new(provider))
+                }
+                Ok(chain_id) => {
+                    return Err(AngstromSdkError::ChainIdMismatch {
+                        provider:   chain_id,
+                        configured: self.chain.chain_id()
+                    });
+                }
+                Err(e) => last_err = e.into()
+            }
+        }
+
+        if providers.is_empty() {
+            return Err(last_err);
+        }
+
+        let client = MultiProviderWrapper::new(providers).into_client();
+        Ok(ProviderBuilder::new().connect_client(client).erased())
+    }
+
+    /// Builds an api with the configured signer and fillers, connected to the
+    /// angstrom node over the configured [`transport`](Self::transport).
+    pub async fn build(
+        &self
+    ) -> Result<AngstromApi<AngstromClient, ConfiguredFillers>, AngstromSdkError> {
+        let api = self.api_builder().await?.build(self.transport()).await?;
+        self.with_fillers(api)
+    }
+
+    async fn api_builder(&self) -> Result<AngstromApiBuilder<DynProvider>, AngstromSdkError> {
+        let headers = HeaderMap::try_from(&self.client.headers)
+            .map_err(|e| AngstromSdkError::Config(format!("invalid headers: {e}")))?;
+
+        let mut builder = AngstromApiBuilder::default()
+            .with_eth_provider(self.connect_eth_provider().await?)
+            .with_url(self.angstrom_url.clone())
+            .with_headers(headers);
+        if let Some(secs) = self.client.request_timeout_secs {
+            builder = builder.with_request_timeout(Duration::from_secs(secs));
+        }
+        if let Some(size) = self.client.max_request_size {
+            builder = builder.with_max_request_size(size);
+        }
+        if let Some(size) = self.client.max_response_size {
+            builder = builder.with_max_response_size(size);
+        }
+        if let Some(secs) = self.client.ws_ping_interval_secs {
+            builder = builder.with_ws_ping_interval(Duration::from_secs(secs));
+        }
+
+        Ok(builder)
+    }
+
+    fn with_fillers<T: AngstromOrderApiClient>(
+        &self,
+        api: AngstromApi<T>
+    ) -> Result<AngstromApi<T, ConfiguredFillers>, AngstromSdkError> {
+        let signer = self.load_signer()?;
+        let api = match &signer {
+            Some(signer) => api.with_wallet(signer.clone()),
+            None => api
+        };
+
+        Ok(api
+            .with_filler(
+                self.fillers
+                    .nonce
+                    .then_some(NonceGeneratorFiller(self.chain))
+            )
+            .with_filler(
+                self.fillers
+                    .balance_check
+                    .then_some(TokenBalanceCheckFiller)
+            )
+            .with_filler(
+                signer
+                    .filter(|_| self.fillers.signer)
+                    .map(AngstromSignerFiller::new)
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_from_toml() {
+        let config = AngstromConfig::from_toml_str(
+            r#"
+            chain = "sepolia"
+            eth_rpc_urls = ["wss://eth.example.com", "https://eth-fallback.example.com"]
+            angstrom_url = "https://angstrom.example.com"
+            transport = "ws"
+
+            [client]
+            request_timeout_secs = 30
+            headers = { Authorization = "Bearer token" }
+
+            [signer]
+            keystore_path = "/keys/angstrom.json"
+            password_env = "KEYSTORE_PASSWORD"
+
+            [fillers]
+            balance_check = false
+            "#
+        )
+        .unwrap();
+
+        assert_eq!(config.chain, AngstromL1Chain::Sepolia);
+        assert_eq!(config.eth_rpc_urls.len(), 2);
+        assert_eq!(config.transport(), AngstromTransport::Ws);
+        assert_eq!(config.client.request_timeout_secs, Some(30));
+        assert_eq!(config.client.headers["Authorization"], "Bearer token");
+        assert_eq!(
+            config.signer,
+            Some(SignerConfig::Keystore {
+                keystore_path: "/keys/angstrom.json".into(),
+                password_env:  "KEYSTORE_PASSWORD".to_owned()
+            })
+        );
+        assert_eq!(
+            config.fillers,
+            FillerConfig { nonce: true, balance_check: false, signer: true }
+        );
+    }
+
+    #[test]
+    fn test_config_from_json() {
+        let config = AngstromConfig::from_json_str(
+            r#"{
+                "eth_rpc_urls": ["https://eth.example.com"],
+                "angstrom_url": "https://angstrom.example.com",
+                "signer": { "private_key_env": "ANGSTROM_PRIVATE_KEY" }
+            }"#
+        )
+        .unwrap();
+
+        assert_eq!(config.chain, AngstromL1Chain::Mainnet);
+        assert_eq!(config.transport(), AngstromTransport::Http);
+        assert_eq!(
+            config.signer,
+            Some(SignerConfig::PrivateKeyEnv {
+                private_key_env: "ANGSTROM_PRIVATE_KEY".to_owned()
+            })
+        );
+        assert_eq!(config.fillers, FillerConfig::default());
+    }
+}
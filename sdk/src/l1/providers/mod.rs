@@ -1,22 +1,48 @@
 pub mod api;
+mod config;
+use std::time::Duration;
 
-use angstrom_types_primitives::primitive::{AngstromAddressBuilder, init_with_chain_id};
+use alloy_primitives::U256;
+use angstrom_types_primitives::{
+    ANGSTROM_DOMAIN,
+    primitive::{AngstromAddressBuilder, try_init_with_chain_id}
+};
 pub use api::AngstromApi;
+pub use config::{
+    AngstromClientConfig, AngstromConfig, AngstromTransport, ConfiguredFillers, FillerConfig,
+    SignerConfig
+};
 pub(crate) mod backend;
 
 use alloy_provider::Provider;
-use jsonrpsee_http_client::HttpClient;
-use jsonrpsee_ws_client::WsClient;
+use jsonrpsee_http_client::{HeaderMap, HttpClient, HttpClientBuilder};
+use jsonrpsee_ws_client::{PingConfig, WsClient, WsClientBuilder};
+
+use crate::l1::{apis::AngstromClient, types::errors::AngstromSdkError};
 
 pub struct AngstromApiBuilder<P: Provider + 'static> {
-    eth_provider:    Option<P>,
-    angstrom_url:    String,
-    address_builder: Option<AngstromAddressBuilder>
+    eth_provider:      Option<P>,
+    angstrom_url:      String,
+    address_builder:   Option<AngstromAddressBuilder>,
+    request_timeout:   Option<Duration>,
+    headers:           HeaderMap,
+    max_request_size:  Option<u32>,
+    max_response_size: Option<u32>,
+    ws_ping_interval:  Option<Duration>
 }
 
 impl<P: Provider + 'static> Default for AngstromApiBuilder<P> {
     fn default() -> Self {
-        Self { eth_provider: None, angstrom_url: "".to_owned(), address_builder: None }
+        Self {
+            eth_provider:      None,
+            angstrom_url:      "".to_owned(),
+            address_builder:   None,
+            request_timeout:   None,
+            headers:           HeaderMap::new(),
+            max_request_size:  None,
+            max_response_size: None,
+            ws_ping_interval:  None
+        }
     }
 }
 
@@ -33,34 +59,146 @@ impl<P: Provider + 'static> AngstromApiBuilder<P> {
         Self { eth_provider: Some(eth_provider), ..self }
     }
 
+    /// Timeout of requests to the angstrom node.
+    pub fn with_request_timeout(self, request_timeout: Duration) -> Self {
+        Self { request_timeout: Some(request_timeout), ..self }
+    }
+
+    /// Headers sent with every request to the angstrom node, such as
+    /// `Authorization`.
+    pub fn with_headers(self, headers: HeaderMap) -> Self {
+        Self { headers, ..self }
+    }
+
+    /// Max size of a request to the angstrom node, in bytes.
+    pub fn with_max_request_size(self, max_request_size: u32) -> Self {
+        Self { max_request_size: Some(max_request_size), ..self }
+    }
+
+    /// Max size of a response from the angstrom node, in bytes.
+    pub fn with_max_response_size(self, max_response_size: u32) -> Self {
+        Self { max_response_size: Some(max_response_size), ..self }
+    }
+
+    /// Pings the angstrom node at `interval` to keep a ws connection alive.
+    pub fn with_ws_ping_interval(self, interval: Duration) -> Self {
+        Self { ws_ping_interval: Some(interval), ..self }
+    }
+
     /// Uses the chain-id of the eth-provider if a address config is not set.
-    pub async fn build_http(self) -> AngstromApi<HttpClient> {
-        assert!(!self.angstrom_url.is_empty());
-        let provider = self.eth_provider.expect("eth provider must be passed in");
+    pub async fn build_http(self) -> Result<AngstromApi<HttpClient>, AngstromSdkError> {
+        let client = self.http_client_builder();
+        let angstrom_url = self.angstrom_url.clone();
+        let provider = self.init_eth_provider().await?;
 
-        if let Some(address_builder) = self.address_builder {
-            address_builder.build().try_init();
-        } else {
-            let chain_id = provider.get_chain_id().await.unwrap();
-            init_with_chain_id(chain_id);
+        Ok(AngstromApi::new_with_providers(provider, client.build(angstrom_url)?))
+    }
+
+    /// Uses the chain-id of the eth-provider if a address config is not set.
+    pub async fn build_ws(self) -> Result<AngstromApi<WsClient>, AngstromSdkError> {
+        let client = self.ws_client_builder();
+        let angstrom_url = self.angstrom_url.clone();
+        let provider = self.init_eth_provider().await?;
+
+        Ok(AngstromApi::new_with_providers(provider, client.build(angstrom_url).await?))
+    }
+
+    /// Connects to the angstrom node over `transport`, for when it is only
+    /// known at runtime.
+    pub async fn build(
+        self,
+        transport: AngstromTransport
+    ) -> Result<AngstromApi<AngstromClient>, AngstromSdkError> {
+        let (http_client, ws_client) = (self.http_client_builder(), self.ws_client_builder());
+        let angstrom_url = self.angstrom_url.clone();
+        let provider = self.init_eth_provider().await?;
+
+        let client = match transport {
+            AngstromTransport::Http => AngstromClient::Http(http_client.build(angstrom_url)?),
+            AngstromTransport::Ws => AngstromClient::Ws(ws_client.build(angstrom_url).await?)
+        };
+        Ok(AngstromApi::new_with_providers(provider, client))
+    }
+
+    fn http_client_builder(&self) -> HttpClientBuilder {
+        let mut client = HttpClient::builder().set_headers(self.headers.clone());
+        if let Some(timeout) = self.request_timeout {
+            client = client.request_timeout(timeout);
+        }
+        if let Some(size) = self.max_request_size {
+            client = client.max_request_size(size);
+        }
+        if let Some(size) = self.max_response_size {
+            client = client.max_response_size(size);
         }
 
-        AngstromApi::new_angstrom_http(provider, &self.angstrom_url).unwrap()
+        client
     }
 
-    pub async fn build_ws(self) -> AngstromApi<WsClient> {
-        assert!(!self.angstrom_url.is_empty());
-        let provider = self.eth_provider.expect("eth provider must be passed in");
+    fn ws_client_builder(&self) -> WsClientBuilder {
+        let mut client = WsClientBuilder::new().set_headers(self.headers.clone());
+        if let Some(timeout) = self.request_timeout {
+            client = client.request_timeout(timeout);
+        }
+        if let Some(size) = self.max_request_size {
+            client = client.max_request_size(size);
+        }
+        if let Some(size) = self.max_response_size {
+            client = client.max_response_size(size);
+        }
+        if let Some(interval) = self.ws_ping_interval {
+            client = client.enable_ws_ping(PingConfig::new().ping_interval(interval));
+        }
+
+        client
+    }
+
+    /// Initializes the angstrom addresses and checks that they are for the
+    /// eth provider's chain.
+    async fn init_eth_provider(self) -> Result<P, AngstromSdkError> {
+        if self.angstrom_url.is_empty() {
+            return Err(AngstromSdkError::Config("angstrom url is not set".to_owned()));
+        }
+        let provider = self
+            .eth_provider
+            .ok_or_else(|| AngstromSdkError::Config("eth provider is not set".to_owned()))?;
+        let chain_id = provider.get_chain_id().await?;
 
         if let Some(address_builder) = self.address_builder {
             address_builder.build().try_init();
         } else {
-            let chain_id = provider.get_chain_id().await.unwrap();
-            init_with_chain_id(chain_id);
+            let _ = try_init_with_chain_id(chain_id);
         }
 
-        AngstromApi::new_angstrom_ws(provider, &self.angstrom_url)
-            .await
-            .unwrap()
+        let configured = ANGSTROM_DOMAIN
+            .get()
+            .and_then(|domain| domain.chain_id)
+            .ok_or_else(|| {
+                AngstromSdkError::Config(format!("no angstrom addresses for chain {chain_id}"))
+            })?;
+        if configured != U256::from(chain_id) {
+            return Err(AngstromSdkError::ChainIdMismatch {
+                provider:   chain_id,
+                configured: configured.saturating_to()
+            });
+        }
+
+        Ok(provider)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_provider::RootProvider;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_build_without_url() {
+        let res = AngstromApiBuilder::<RootProvider>::default()
+            .build_http()
+            .await;
+
+        assert!(matches!(res, Err(AngstromSdkError::Config(_))));
     }
 }
@@ -11,7 +11,7 @@ use uniswap_storage::{angstrom::mainnet::ANGSTROM_L1_CONSTANTS_MAINNET, v4::Unpa
 
 use crate::l1::test_utils::{USDC, WETH};
 #[cfg(not(feature = "local-reth"))]
-use crate::types::providers::AlloyProviderWrapper;
+use crate::types::providers::{AlloyProviderWrapper, NodeOrReplayProvider};
 #[cfg(feature = "local-reth")]
 use crate::types::{MainnetExt, providers::RethDbProviderWrapper};
 
@@ -31,21 +31,26 @@ pub struct ValidPositionTestParameters {
     pub valid_block_after_swaps: u64
 }
 
+/// The l1 provider of the tests, replaying `fixtures/l1.json` when
+/// `ETH_WS_URL` is not set. See [`NodeOrReplayProvider`] to record it.
 #[cfg(not(feature = "local-reth"))]
 pub async fn init_valid_position_params_with_provider()
--> (AlloyProviderWrapper, ValidPositionTestParameters) {
-    use alloy_provider::Provider;
+-> (NodeOrReplayProvider, ValidPositionTestParameters) {
+    use alloy_provider::{RootProvider, WsConnect};
 
     let params = init_valid_position_params();
-    let provider = crate::l1::test_utils::spawn_angstrom_api()
-        .await
-        .unwrap()
-        .eth_provider()
-        .clone()
-        .root()
-        .clone();
+    dotenv::dotenv().ok();
+    let url = std::env::var("ETH_WS_URL").ok();
+    let provider = NodeOrReplayProvider::connect_or_replay(url, "l1.json", |url| async move {
+        let provider: RootProvider = RootProvider::builder()
+            .connect_ws(WsConnect::new(url))
+            .await?;
+        eyre::Ok(AlloyProviderWrapper::new(provider))
+    })
+    .await
+    .unwrap();
 
-    (AlloyProviderWrapper::new(provider), params)
+    (provider, params)
 }
 
 #[cfg(feature = "local-reth")]
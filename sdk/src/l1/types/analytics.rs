@@ -0,0 +1,483 @@
+use std::collections::{BTreeMap, HashMap};
+
+use alloy_primitives::{Address, U256};
+use angstrom_types_primitives::{contract_bindings::pool_manager::PoolManager, primitive::PoolId};
+use serde::{Deserialize, Serialize};
+use uni_v4::L1FeeConfiguration;
+
+use crate::{
+    l1::types::{DecodedBundle, DecodedOrderFill, DecodedOrderKind},
+    types::common::BucketInterval
+};
+
+const FEE_E6: u64 = 1_000_000;
+
+/// An amount of each of the pool's tokens.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TokenAmounts {
+    pub token0: U256,
+    pub token1: U256
+}
+
+impl TokenAmounts {
+    fn add(&mut self, token0: U256, token1: U256) {
+        self.token0 += token0;
+        self.token1 += token1;
+    }
+}
+
+/// Open, high, low and close of a price.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PriceOhlc {
+    pub open:  U256,
+    pub high:  U256,
+    pub low:   U256,
+    pub close: U256
+}
+
+impl PriceOhlc {
+    fn new(price: U256) -> Self {
+        Self { open: price, high: price, low: price, close: price }
+    }
+
+    fn update(&mut self, price: U256) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+    }
+}
+
+/// A pool's activity over one bucket.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PoolAnalyticsBucket {
+    pub pool_id:         PoolId,
+    /// the first block number or timestamp of the bucket
+    pub bucket_start:    u64,
+    pub tob_volume:      TokenAmounts,
+    pub user_volume:     TokenAmounts,
+    pub unlocked_volume: TokenAmounts,
+    /// bundle rewards (token0) plus the unlocked swap fee on swap inputs
+    pub lp_fees:         TokenAmounts,
+    /// the protocol fee on unlocked swap inputs
+    pub protocol_fees:   TokenAmounts,
+    /// the bundle clearing price, token1 over token0 in ray
+    pub clearing_price:  Option<PriceOhlc>,
+    pub bundles:         u64,
+    pub tob_orders:      u64,
+    pub user_orders:     u64,
+    pub unlocked_swaps:  u64
+}
+
+impl PoolAnalyticsBucket {
+    fn new(pool_id: PoolId, bucket_start: u64) -> Self {
+        Self {
+            pool_id,
+            bucket_start,
+            tob_volume: TokenAmounts::default(),
+            user_volume: TokenAmounts::default(),
+            unlocked_volume: TokenAmounts::default(),
+            lp_fees: TokenAmounts::default(),
+            protocol_fees: TokenAmounts::default(),
+            clearing_price: None,
+            bundles: 0,
+            tob_orders: 0,
+            user_orders: 0,
+            unlocked_swaps: 0
+        }
+    }
+}
+
+/// Folds decoded bundles and unlocked swaps into [`PoolAnalyticsBucket`]s.
+/// Bundles and swaps must be added in block order for the prices to open and
+/// close correctly.
+#[derive(Debug, Clone)]
+pub struct PoolAnalyticsAggregator {
+    interval:    BucketInterval,
+    pools:       HashMap<(Address, Address), PoolId>,
+    fee_configs: HashMap<PoolId, L1FeeConfiguration>,
+    buckets:     BTreeMap<(PoolId, u64), PoolAnalyticsBucket>
+}
+
+impl PoolAnalyticsAggregator {
+    /// `pools` are the pools to aggregate, with their tokens and fee
+    /// configuration.
+    pub fn new(
+        interval: BucketInterval,
+        pools: impl IntoIterator<Item = (PoolId, Address, Address, L1FeeConfiguration)>
+    ) -> Self {
+        let mut this = Self {
+            interval,
+            pools: HashMap::default(),
+            fee_configs: HashMap::default(),
+            buckets: BTreeMap::default()
+        };
+
+        for (pool_id, token0, token1, fee_config) in pools {
+            this.pools.insert((token0, token1), pool_id);
+            this.fee_configs.insert(pool_id, fee_config);
+        }
+
+        this
+    }
+
+    pub fn add_bundle(&mut self, bundle: &DecodedBundle, block_timestamp: u64) {
+        let bucket_start = self
+            .interval
+            .bucket_start(bundle.block_number, block_timestamp);
+
+        for update in &bundle.pool_updates {
+            let Some(&pool_id) = self.pools.get(&(update.token0, update.token1)) else {
+                continue;
+            };
+            let bucket = self.bucket(pool_id, bucket_start);
+            bucket
+                .lp_fees
+                .add(U256::from(update.rewards.total()), U256::ZERO);
+        }
+
+        let mut pools_in_bundle = Vec::new();
+        for fill in bundle.all_fills() {
+            let (token0, token1) = fill_tokens(fill);
+            let Some(&pool_id) = self.pools.get(&(token0, token1)) else { continue };
+            let (amount0, amount1) = fill_amounts(fill);
+
+            let bucket = self.bucket(pool_id, bucket_start);
+            if fill.kind == DecodedOrderKind::TopOfBlock {
+                bucket.tob_volume.add(amount0, amount1);
+                bucket.tob_orders += 1;
+            } else {
+                bucket.user_volume.add(amount0, amount1);
+                bucket.user_orders += 1;
+            }
+
+            if !pools_in_bundle.contains(&pool_id) {
+                pools_in_bundle.push(pool_id);
+                bucket.bundles += 1;
+                match bucket.clearing_price.as_mut() {
+                    Some(ohlc) => ohlc.update(fill.price_1over0),
+                    None => bucket.clearing_price = Some(PriceOhlc::new(fill.price_1over0))
+                }
+            }
+        }
+    }
+
+    pub fn add_unlocked_swap(
+        &mut self,
+        swap: &PoolManager::Swap,
+        block_number: u64,
+        block_timestamp: u64
+    ) {
+        let Some(fee_config) = self.fee_configs.get(&swap.id).cloned() else { return };
+        let bucket_start = self.interval.bucket_start(block_number, block_timestamp);
+        let bucket = self.bucket(swap.id, bucket_start);
+
+        // swap amounts are the swapper's balance deltas, so the input is
+        // negative
+        let amount0 = U256::from(swap.amount0.unsigned_abs());
+        let amount1 = U256::from(swap.amount1.unsigned_abs());
+        bucket.unlocked_volume.add(amount0, amount1);
+        bucket.unlocked_swaps += 1;
+
+        let (input0, input1) =
+            if swap.amount0 < 0 { (amount0, U256::ZERO) } else { (U256::ZERO, amount1) };
+        let fee = |amount: U256, fee_e6: u32| amount * U256::from(fee_e6) / U256::from(FEE_E6);
+        bucket
+            .lp_fees
+            .add(fee(input0, fee_config.swap_fee), fee(input1, fee_config.swap_fee));
+        bucket
+            .protocol_fees
+            .add(fee(input0, fee_config.protocol_fee), fee(input1, fee_config.protocol_fee));
+    }
+
+    /// The buckets, ordered by pool and bucket start.
+    pub fn finish(self) -> Vec<PoolAnalyticsBucket> {
+        self.buckets.into_values().collect()
+    }
+
+    fn bucket(&mut self, pool_id: PoolId, bucket_start: u64) -> &mut PoolAnalyticsBucket {
+        self.buckets
+            .entry((pool_id, bucket_start))
+            .or_insert_with(|| PoolAnalyticsBucket::new(pool_id, bucket_start))
+    }
+}
+
+/// An OHLCV candle. Prices are token1 per token0 and volumes are in whole
+/// tokens, both adjusted for the tokens' decimals.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Candle {
+    /// the first block number or timestamp of the candle
+    pub start:   u64,
+    pub open:    f64,
+    pub high:    f64,
+    pub low:     f64,
+    pub close:   f64,
+    pub volume0: f64,
+    pub volume1: f64,
+    /// the number of bundles and unlocked swaps in the candle
+    pub trades:  u64
+}
+
+/// Folds bundle clearing prices and unlocked swap prices into [`Candle`]s.
+/// Prices must be added in execution order. Buckets without any trade have
+/// no candle.
+#[derive(Debug, Clone)]
+pub struct CandleBuilder {
+    interval:  BucketInterval,
+    decimals0: u8,
+    decimals1: u8,
+    candles:   BTreeMap<u64, Candle>
+}
+
+impl CandleBuilder {
+    pub fn new(interval: BucketInterval, decimals0: u8, decimals1: u8) -> Self {
+        Self { interval, decimals0, decimals1, candles: BTreeMap::default() }
+    }
+
+    /// Adds a bundle's clearing price (token1 over token0 in ray) and the
+    /// amounts its orders traded in the pool.
+    pub fn add_bundle(
+        &mut self,
+        block_number: u64,
+        block_timestamp: u64,
+        price_1over0: U256,
+        amount0: U256,
+        amount1: U256
+    ) {
+        let price = f64::from(price_1over0) / 1e27;
+        self.add(block_number, block_timestamp, price, amount0, amount1);
+    }
+
+    /// Adds an unlocked swap's post-swap price and its amounts.
+    pub fn add_swap(&mut self, swap: &PoolManager::Swap, block_number: u64, block_timestamp: u64) {
+        let sqrt_price = f64::from(U256::from(swap.sqrtPriceX96)) / 2f64.powi(96);
+        self.add(
+            block_number,
+            block_timestamp,
+            sqrt_price * sqrt_price,
+            U256::from(swap.amount0.unsigned_abs()),
+            U256::from(swap.amount1.unsigned_abs())
+        );
+    }
+
+    pub fn finish(self) -> Vec<Candle> {
+        self.candles.into_values().collect()
+    }
+
+    fn add(
+        &mut self,
+        block_number: u64,
+        block_timestamp: u64,
+        raw_price: f64,
+        amount0: U256,
+        amount1: U256
+    ) {
+        let price = raw_price * 10f64.powi(self.decimals0 as i32 - self.decimals1 as i32);
+        let volume0 = f64::from(amount0) / 10f64.powi(self.decimals0 as i32);
+        let volume1 = f64::from(amount1) / 10f64.powi(self.decimals1 as i32);
+        let start = self.interval.bucket_start(block_number, block_timestamp);
+
+        let candle = self.candles.entry(start).or_insert(Candle {
+            start,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume0: 0.0,
+            volume1: 0.0,
+            trades: 0
+        });
+        candle.high = candle.high.max(price);
+        candle.low = candle.low.min(price);
+        candle.close = price;
+        candle.volume0 += volume0;
+        candle.volume1 += volume1;
+        candle.trades += 1;
+    }
+}
+
+fn fill_tokens(fill: &DecodedOrderFill) -> (Address, Address) {
+    if fill.zero_for_one {
+        (fill.token_in, fill.token_out)
+    } else {
+        (fill.token_out, fill.token_in)
+    }
+}
+
+fn fill_amounts(fill: &DecodedOrderFill) -> (U256, U256) {
+    if fill.zero_for_one {
+        (fill.amount_in, fill.amount_out)
+    } else {
+        (fill.amount_out, fill.amount_in)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{
+        B256, address,
+        aliases::{I24, U24}
+    };
+
+    use super::*;
+    use crate::l1::types::{DecodedPoolUpdate, DecodedRewardsUpdate};
+
+    const TOKEN0: Address = address!("0x1111111111111111111111111111111111111111");
+    const TOKEN1: Address = address!("0x2222222222222222222222222222222222222222");
+    const POOL_ID: PoolId = B256::with_last_byte(1);
+
+    fn fill(kind: DecodedOrderKind, zero_for_one: bool, price: u64) -> DecodedOrderFill {
+        let (token_in, token_out) = if zero_for_one { (TOKEN0, TOKEN1) } else { (TOKEN1, TOKEN0) };
+        DecodedOrderFill {
+            kind,
+            order_hash: B256::ZERO,
+            signer: None,
+            recipient: None,
+            token_in,
+            token_out,
+            zero_for_one,
+            exact_in: true,
+            amount_in: U256::from(100),
+            amount_out: U256::from(50),
+            fee_asset0: 0,
+            price_1over0: U256::from(price)
+        }
+    }
+
+    fn bundle(block_number: u64, price: u64) -> DecodedBundle {
+        DecodedBundle {
+            block_number,
+            assets: vec![],
+            top_of_block_orders: vec![fill(DecodedOrderKind::TopOfBlock, true, price)],
+            user_orders: vec![
+                fill(DecodedOrderKind::Flash, false, price),
+                fill(DecodedOrderKind::Standing, true, price),
+            ],
+            pool_updates: vec![DecodedPoolUpdate {
+                token0:           TOKEN0,
+                token1:           TOKEN1,
+                zero_for_one:     true,
+                swap_in_quantity: 0,
+                rewards:          DecodedRewardsUpdate::CurrentOnly {
+                    amount:             7,
+                    expected_liquidity: 0
+                }
+            }]
+        }
+    }
+
+    #[test]
+    fn test_pool_analytics_aggregator() {
+        let fee_config =
+            L1FeeConfiguration { bundle_fee: 0, swap_fee: 10_000, protocol_fee: 5_000 };
+        let mut aggregator = PoolAnalyticsAggregator::new(
+            BucketInterval::Blocks(10),
+            [(POOL_ID, TOKEN0, TOKEN1, fee_config)]
+        );
+
+        aggregator.add_bundle(&bundle(101, 3), 0);
+        aggregator.add_bundle(&bundle(105, 5), 0);
+        aggregator.add_bundle(&bundle(108, 2), 0);
+        aggregator.add_bundle(&bundle(111, 4), 0);
+        aggregator.add_unlocked_swap(
+            &PoolManager::Swap {
+                id:           POOL_ID,
+                sender:       Address::ZERO,
+                amount0:      -1000,
+                amount1:      400,
+                sqrtPriceX96: Default::default(),
+                liquidity:    0,
+                tick:         I24::ZERO,
+                fee:          U24::from(10_000)
+            },
+            109,
+            0
+        );
+
+        let buckets = aggregator.finish();
+        assert_eq!(buckets.len(), 2);
+
+        let first = &buckets[0];
+        assert_eq!(first.bucket_start, 100);
+        assert_eq!(first.bundles, 3);
+        assert_eq!((first.tob_orders, first.user_orders, first.unlocked_swaps), (3, 6, 1));
+        assert_eq!(
+            first.tob_volume,
+            TokenAmounts { token0: U256::from(300), token1: U256::from(150) }
+        );
+        assert_eq!(
+            first.user_volume,
+            TokenAmounts { token0: U256::from(450), token1: U256::from(450) }
+        );
+        assert_eq!(
+            first.unlocked_volume,
+            TokenAmounts { token0: U256::from(1000), token1: U256::from(400) }
+        );
+        assert_eq!(first.lp_fees, TokenAmounts { token0: U256::from(31), token1: U256::ZERO });
+        assert_eq!(first.protocol_fees, TokenAmounts { token0: U256::from(5), token1: U256::ZERO });
+        assert_eq!(
+            first.clearing_price,
+            Some(PriceOhlc {
+                open:  U256::from(3),
+                high:  U256::from(5),
+                low:   U256::from(2),
+                close: U256::from(2)
+            })
+        );
+
+        assert_eq!(buckets[1].bucket_start, 110);
+        assert_eq!(buckets[1].clearing_price.unwrap().open, U256::from(4));
+    }
+
+    #[test]
+    fn test_candle_builder() {
+        let ray = U256::from(10).pow(U256::from(27));
+        // token0 has 18 decimals and token1 has 6
+        let mut builder = CandleBuilder::new(BucketInterval::Seconds(60), 18, 6);
+
+        // 2000 token1 per token0
+        builder.add_bundle(
+            10,
+            120,
+            ray * U256::from(2000) / U256::from(10).pow(U256::from(12)),
+            U256::from(10).pow(U256::from(18)),
+            U256::from(2_000_000_000u64)
+        );
+        builder.add_swap(
+            &PoolManager::Swap {
+                id:           POOL_ID,
+                sender:       Address::ZERO,
+                amount0:      -500_000_000_000_000_000,
+                amount1:      1_100_000_000,
+                // sqrt(2200e-12) * 2^96
+                sqrtPriceX96: "3716130220787572886470656".parse().unwrap(),
+                liquidity:    0,
+                tick:         I24::ZERO,
+                fee:          U24::ZERO
+            },
+            11,
+            150
+        );
+        builder.add_bundle(
+            12,
+            185,
+            ray / U256::from(10).pow(U256::from(9)),
+            U256::ZERO,
+            U256::ZERO
+        );
+
+        let candles = builder.finish();
+        assert_eq!(candles.len(), 2);
+
+        let first = candles[0];
+        assert_eq!((first.start, first.trades), (120, 2));
+        assert!((first.open - 2000.0).abs() < 1e-6);
+        assert!((first.high - 2200.0).abs() < 1e-3);
+        assert!((first.close - 2200.0).abs() < 1e-3);
+        assert!((first.low - 2000.0).abs() < 1e-6);
+        assert!((first.volume0 - 1.5).abs() < 1e-9);
+        assert!((first.volume1 - 3100.0).abs() < 1e-9);
+
+        assert_eq!(candles[1].start, 180);
+        assert!((candles[1].open - 1000.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+pub use _erc20::Transfer as Erc20Transfer;
+use alloy_primitives::{Address, I256, Log, TxHash, U256};
+use alloy_sol_types::SolEvent;
+
+mod _erc20 {
+    alloy_sol_types::sol! {
+        /// ERC20 transfer, used to resolve token balance changes from replayed
+        /// logs
+        #[derive(Debug, PartialEq, Eq)]
+        event Transfer(address indexed from, address indexed to, uint256 value);
+    }
+}
+
+/// The effects of re-executing an Angstrom bundle transaction on top of the
+/// state it was originally executed against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleReplay {
+    pub block_number:    u64,
+    pub tx_hash:         TxHash,
+    pub success:         bool,
+    pub gas_used:        u64,
+    pub logs:            Vec<Log>,
+    /// contract -> slot -> value before and after the bundle
+    pub storage_diff:    HashMap<Address, HashMap<U256, StorageSlotDiff>>,
+    /// holder -> token -> balance change. Native ETH is keyed by
+    /// `Address::ZERO` and includes the gas paid by the sender.
+    pub balance_changes: HashMap<Address, HashMap<Address, I256>>,
+    /// the transactions before the bundle in its block that revm rejected,
+    /// whose effects are missing from the state the bundle ran against
+    pub skipped_txs:     Vec<TxHash>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageSlotDiff {
+    pub before: U256,
+    pub after:  U256
+}
+
+impl BundleReplay {
+    /// The balance change of `holder` in `token` (`Address::ZERO` for ETH).
+    pub fn balance_change(&self, holder: Address, token: Address) -> I256 {
+        self.balance_changes
+            .get(&holder)
+            .and_then(|tokens| tokens.get(&token))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Accumulates the ERC20 transfers in `logs` into `balance_changes`.
+    pub(crate) fn add_erc20_transfers(&mut self) {
+        for log in &self.logs {
+            let Ok(transfer) = Erc20Transfer::decode_log(log) else { continue };
+            let value = I256::from_raw(transfer.value);
+
+            add_balance_change(&mut self.balance_changes, transfer.from, log.address, -value);
+            add_balance_change(&mut self.balance_changes, transfer.to, log.address, value);
+        }
+    }
+}
+
+pub(crate) fn add_balance_change(
+    balance_changes: &mut HashMap<Address, HashMap<Address, I256>>,
+    holder: Address,
+    token: Address,
+    change: I256
+) {
+    if change.is_zero() {
+        return;
+    }
+
+    let entry = balance_changes
+        .entry(holder)
+        .or_default()
+        .entry(token)
+        .or_default();
+    *entry += change;
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{LogData, address};
+
+    use super::*;
+
+    #[test]
+    fn test_add_erc20_transfers() {
+        let token = address!("0x1111111111111111111111111111111111111111");
+        let from = address!("0x2222222222222222222222222222222222222222");
+        let to = address!("0x3333333333333333333333333333333333333333");
+
+        let transfer = Erc20Transfer { from, to, value: U256::from(100) };
+        let log = Log { address: token, data: LogData::from(&transfer) };
+
+        let mut replay = BundleReplay {
+            block_number:    0,
+            tx_hash:         TxHash::ZERO,
+            success:         true,
+            gas_used:        0,
+            logs:            vec![log.clone(), log],
+            storage_diff:    HashMap::default(),
+            balance_changes: HashMap::default(),
+            skipped_txs:     Vec::new()
+        };
+        replay.add_erc20_transfers();
+
+        assert_eq!(replay.balance_change(from, token), I256::try_from(-200).unwrap());
+        assert_eq!(replay.balance_change(to, token), I256::try_from(200).unwrap());
+        assert_eq!(replay.balance_change(to, Address::ZERO), I256::ZERO);
+    }
+}
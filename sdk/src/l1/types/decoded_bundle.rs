@@ -0,0 +1,392 @@
+use alloy_primitives::{Address, B256, U256, aliases::I24};
+use angstrom_types_primitives::contract_payloads::{
+    Asset, Pair, Signature,
+    angstrom::{AngstromBundle, OrderQuantities, TopOfBlockOrder, UserOrder},
+    rewards::RewardsUpdate
+};
+use serde::{Deserialize, Serialize};
+use uniswap_storage::v4::utils::mul_div;
+
+/// Scale of `Pair::price_1over0`
+const RAY: U256 = U256::from_limbs([11515845246265065472, 54210108, 0, 0]);
+
+/// A human-readable view of an [`AngstromBundle`], with every index resolved
+/// to token addresses and every order resolved to its fill.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DecodedBundle {
+    pub block_number:        u64,
+    pub assets:              Vec<DecodedAsset>,
+    pub top_of_block_orders: Vec<DecodedOrderFill>,
+    pub user_orders:         Vec<DecodedOrderFill>,
+    pub pool_updates:        Vec<DecodedPoolUpdate>
+}
+
+impl DecodedBundle {
+    /// `block_number` is the block the bundle was executed in, which is part
+    /// of the hash of flash and TOB orders.
+    ///
+    /// Errors if a pair or asset index of the bundle is out of bounds, as in
+    /// a malformed bundle.
+    pub fn new(bundle: &AngstromBundle, block_number: u64) -> eyre::Result<Self> {
+        let assets = bundle.assets.iter().map(DecodedAsset::from).collect();
+
+        let top_of_block_orders = bundle
+            .top_of_block_orders
+            .iter()
+            .map(|order| DecodedOrderFill::from_tob(order, bundle, block_number))
+            .collect::<eyre::Result<_>>()?;
+
+        let user_orders = bundle
+            .user_orders
+            .iter()
+            .map(|order| DecodedOrderFill::from_user(order, bundle, block_number))
+            .collect::<eyre::Result<_>>()?;
+
+        let pool_updates = bundle
+            .pool_updates
+            .iter()
+            .map(|update| {
+                let pair = bundle_pair(bundle, update.pair_index as usize)?;
+                let (token0, token1) = pair_tokens(pair, &bundle.assets)?;
+
+                Ok(DecodedPoolUpdate {
+                    token0,
+                    token1,
+                    zero_for_one: update.zero_for_one,
+                    swap_in_quantity: update.swap_in_quantity,
+                    rewards: DecodedRewardsUpdate::from(&update.rewards_update)
+                })
+            })
+            .collect::<eyre::Result<_>>()?;
+
+        Ok(Self { block_number, assets, top_of_block_orders, user_orders, pool_updates })
+    }
+
+    /// All order fills, top of block orders first.
+    pub fn all_fills(&self) -> impl Iterator<Item = &DecodedOrderFill> {
+        self.top_of_block_orders.iter().chain(&self.user_orders)
+    }
+}
+
+/// The per-asset accounting of a bundle.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DecodedAsset {
+    pub token:  Address,
+    /// amount saved to the Angstrom contract as fees
+    pub save:   u128,
+    /// amount taken from the PoolManager
+    pub take:   u128,
+    /// amount settled back to the PoolManager
+    pub settle: u128
+}
+
+impl From<&Asset> for DecodedAsset {
+    fn from(value: &Asset) -> Self {
+        Self { token: value.addr, save: value.save, take: value.take, settle: value.settle }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DecodedOrderKind {
+    TopOfBlock,
+    Standing,
+    Flash
+}
+
+/// A single order's fill in a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DecodedOrderFill {
+    pub kind:         DecodedOrderKind,
+    pub order_hash:   B256,
+    pub signer:       Option<Address>,
+    /// the signer, unless the order sets a different recipient
+    pub recipient:    Option<Address>,
+    pub token_in:     Address,
+    pub token_out:    Address,
+    pub zero_for_one: bool,
+    pub exact_in:     bool,
+    pub amount_in:    U256,
+    pub amount_out:   U256,
+    /// the gas fee paid, in token0
+    pub fee_asset0:   u128,
+    /// the pair's uniform clearing price, token1 over token0 in ray
+    pub price_1over0: U256
+}
+
+impl DecodedOrderFill {
+    fn from_tob(
+        order: &TopOfBlockOrder,
+        bundle: &AngstromBundle,
+        block_number: u64
+    ) -> eyre::Result<Self> {
+        let pair = bundle_pair(bundle, order.pairs_index as usize)?;
+        let (token_in, token_out) = in_out_tokens(pair, &bundle.assets, order.zero_for_1)?;
+        let order_hash = order.order_hash(&bundle.pairs, &bundle.assets, block_number);
+        let signer = recover_signer(&order.signature, order_hash);
+
+        Ok(Self {
+            kind: DecodedOrderKind::TopOfBlock,
+            order_hash,
+            signer,
+            recipient: order.recipient.or(signer),
+            token_in,
+            token_out,
+            zero_for_one: order.zero_for_1,
+            exact_in: true,
+            amount_in: U256::from(order.quantity_in),
+            amount_out: U256::from(order.quantity_out),
+            fee_asset0: order.gas_used_asset_0,
+            price_1over0: pair.price_1over0
+        })
+    }
+
+    fn from_user(
+        order: &UserOrder,
+        bundle: &AngstromBundle,
+        block_number: u64
+    ) -> eyre::Result<Self> {
+        let pair = bundle_pair(bundle, order.pair_index as usize)?;
+        let (token_in, token_out) = in_out_tokens(pair, &bundle.assets, order.zero_for_one)?;
+        let order_hash = order.order_hash(&bundle.pairs, &bundle.assets, block_number);
+        let signer = recover_signer(&order.signature, order_hash);
+
+        let quantity = match order.order_quantities {
+            OrderQuantities::Exact { quantity } => quantity,
+            OrderQuantities::Partial { filled_quantity, .. } => filled_quantity
+        };
+        let (amount_in, amount_out) = user_order_amounts(
+            U256::from(quantity),
+            U256::from(order.extra_fee_asset0),
+            pair.price_1over0,
+            order.zero_for_one,
+            order.exact_in
+        );
+
+        Ok(Self {
+            kind: if order.standing_validation.is_some() {
+                DecodedOrderKind::Standing
+            } else {
+                DecodedOrderKind::Flash
+            },
+            order_hash,
+            signer,
+            recipient: order.recipient.or(signer),
+            token_in,
+            token_out,
+            zero_for_one: order.zero_for_one,
+            exact_in: order.exact_in,
+            amount_in,
+            amount_out,
+            fee_asset0: order.extra_fee_asset0,
+            price_1over0: pair.price_1over0
+        })
+    }
+}
+
+/// A pool's swap and reward distribution in a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DecodedPoolUpdate {
+    pub token0:           Address,
+    pub token1:           Address,
+    pub zero_for_one:     bool,
+    pub swap_in_quantity: u128,
+    pub rewards:          DecodedRewardsUpdate
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum DecodedRewardsUpdate {
+    /// Rewards donated across the ticks starting at `start_tick`
+    MultiTick { start_tick: I24, start_liquidity: u128, quantities: Vec<u128> },
+    /// Rewards donated to the current tick only
+    CurrentOnly { amount: u128, expected_liquidity: u128 }
+}
+
+impl DecodedRewardsUpdate {
+    /// The total amount of token0 donated to LPs.
+    pub fn total(&self) -> u128 {
+        match self {
+            DecodedRewardsUpdate::MultiTick { quantities, .. } => quantities.iter().sum(),
+            DecodedRewardsUpdate::CurrentOnly { amount, .. } => *amount
+        }
+    }
+}
+
+impl From<&RewardsUpdate> for DecodedRewardsUpdate {
+    fn from(value: &RewardsUpdate) -> Self {
+        match value {
+            RewardsUpdate::MultiTick { start_tick, start_liquidity, quantities, .. } => {
+                DecodedRewardsUpdate::MultiTick {
+                    start_tick:      *start_tick,
+                    start_liquidity: *start_liquidity,
+                    quantities:      quantities.clone()
+                }
+            }
+            RewardsUpdate::CurrentOnly { amount, expected_liquidity } => {
+                DecodedRewardsUpdate::CurrentOnly {
+                    amount:             *amount,
+                    expected_liquidity: *expected_liquidity
+                }
+            }
+        }
+    }
+}
+
+fn bundle_pair(bundle: &AngstromBundle, pair_index: usize) -> eyre::Result<&Pair> {
+    bundle.pairs.get(pair_index).ok_or_else(|| {
+        eyre::eyre!("pair index {pair_index} out of bounds of {} pairs", bundle.pairs.len())
+    })
+}
+
+fn pair_tokens(pair: &Pair, assets: &[Asset]) -> eyre::Result<(Address, Address)> {
+    let asset = |index: usize| {
+        assets.get(index).map(|asset| asset.addr).ok_or_else(|| {
+            eyre::eyre!("asset index {index} out of bounds of {} assets", assets.len())
+        })
+    };
+    Ok((asset(pair.index0 as usize)?, asset(pair.index1 as usize)?))
+}
+
+fn in_out_tokens(
+    pair: &Pair,
+    assets: &[Asset],
+    zero_for_one: bool
+) -> eyre::Result<(Address, Address)> {
+    let (token0, token1) = pair_tokens(pair, assets)?;
+    Ok(if zero_for_one { (token0, token1) } else { (token1, token0) })
+}
+
+/// Resolves a user order's amount in and out at the pair's clearing price.
+/// The gas fee is charged in token0, so it is taken from whichever side of
+/// the swap is token0. Amounts are rounded down.
+fn user_order_amounts(
+    quantity: U256,
+    fee_asset0: U256,
+    price_1over0: U256,
+    zero_for_one: bool,
+    exact_in: bool
+) -> (U256, U256) {
+    if price_1over0.is_zero() {
+        return (U256::ZERO, U256::ZERO);
+    }
+
+    match (zero_for_one, exact_in) {
+        (true, true) => (quantity, mul_div(quantity.saturating_sub(fee_asset0), price_1over0, RAY)),
+        (true, false) => (mul_div(quantity, RAY, price_1over0) + fee_asset0, quantity),
+        (false, true) => {
+            (quantity, mul_div(quantity, RAY, price_1over0).saturating_sub(fee_asset0))
+        }
+        (false, false) => (mul_div(quantity + fee_asset0, price_1over0, RAY), quantity)
+    }
+}
+
+fn recover_signer(signature: &Signature, order_hash: B256) -> Option<Address> {
+    match signature {
+        Signature::Contract { from, .. } => Some(*from),
+        Signature::Ecdsa { v, r, s } => {
+            alloy_primitives::Signature::from_scalars_and_parity(*r, *s, *v != 27 && *v != 0)
+                .recover_address_from_prehash(&order_hash)
+                .ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{
+        Bytes, address,
+        aliases::{I24, U24}
+    };
+    use angstrom_types_primitives::{
+        contract_bindings::pool_manager::PoolManager::PoolKey,
+        sol_bindings::{
+            grouped_orders::AllOrders,
+            rpc_orders::{OrderMeta, TopOfBlockOrder as RpcTopOfBlockOrder}
+        }
+    };
+
+    use super::*;
+    use crate::{l1::builders::BundleBuilder, types::common::PoolMetadata};
+
+    #[test]
+    fn test_malformed_bundle() {
+        let (token0, token1) = (
+            address!("0x1111111111111111111111111111111111111111"),
+            address!("0x2222222222222222222222222222222222222222")
+        );
+        let pool_key = PoolKey {
+            currency0:   token0,
+            currency1:   token1,
+            fee:         U24::ZERO,
+            tickSpacing: I24::unchecked_from(60),
+            hooks:       Address::ZERO
+        };
+        let tob = RpcTopOfBlockOrder {
+            quantity_in: 100,
+            quantity_out: 200,
+            asset_in: token1,
+            asset_out: token0,
+            meta: OrderMeta {
+                isEcdsa:   false,
+                from:      token0,
+                signature: Bytes::from_static(&[1, 2, 3])
+            },
+            ..Default::default()
+        };
+        let mut bundle = BundleBuilder::new()
+            .with_pool(
+                PoolMetadata {
+                    pool_key,
+                    pool_id: pool_key.into(),
+                    token0,
+                    token1,
+                    fee: 0,
+                    tick_spacing: 60,
+                    storage_idx: 0
+                },
+                RAY
+            )
+            .with_order(AllOrders::TOB(tob))
+            .build()
+            .unwrap();
+        assert!(DecodedBundle::new(&bundle, 1).is_ok());
+
+        bundle.assets.truncate(1);
+        assert!(DecodedBundle::new(&bundle, 1).is_err());
+
+        bundle.pairs.clear();
+        assert!(DecodedBundle::new(&bundle, 1).is_err());
+    }
+
+    #[test]
+    fn test_ray() {
+        assert_eq!(RAY, U256::from(10).pow(U256::from(27)));
+    }
+
+    #[test]
+    fn test_user_order_amounts() {
+        // 1 token0 = 2 token1
+        let price = RAY * U256::from(2);
+        let fee = U256::from(10);
+
+        assert_eq!(
+            user_order_amounts(U256::from(110), fee, price, true, true),
+            (U256::from(110), U256::from(200))
+        );
+        assert_eq!(
+            user_order_amounts(U256::from(200), fee, price, true, false),
+            (U256::from(110), U256::from(200))
+        );
+        assert_eq!(
+            user_order_amounts(U256::from(220), fee, price, false, true),
+            (U256::from(220), U256::from(100))
+        );
+        assert_eq!(
+            user_order_amounts(U256::from(100), fee, price, false, false),
+            (U256::from(220), U256::from(100))
+        );
+        assert_eq!(
+            user_order_amounts(U256::from(100), fee, U256::ZERO, true, true),
+            (U256::ZERO, U256::ZERO)
+        );
+    }
+}
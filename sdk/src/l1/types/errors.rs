@@ -1,4 +1,7 @@
 use alloy_json_rpc::RpcError;
+use alloy_primitives::TxHash;
+use alloy_provider::PendingTransactionError;
+use alloy_sol_types::decode_revert_reason;
 use alloy_transport::TransportErrorKind;
 
 #[derive(Debug, thiserror::Error)]
@@ -12,5 +15,34 @@ pub enum AngstromSdkError {
     #[error("angstrom-rpc error: {0:?}")]
     AngstromRpc(String),
     #[error(transparent)]
-    Deser(#[from] serde_json::Error)
+    Deser(#[from] serde_json::Error),
+    #[error("pending transaction error: {0:?}")]
+    PendingTransaction(#[from] PendingTransactionError),
+    #[error("execution reverted: {0}")]
+    Reverted(String),
+    #[error("transaction {0:?} failed")]
+    TransactionFailed(TxHash),
+    #[error("no wallet set, call `with_wallet` first")]
+    MissingWallet,
+    #[error("invalid configuration: {0}")]
+    Config(String),
+    #[error("eth provider chain {provider} does not match angstrom chain {configured}")]
+    ChainIdMismatch { provider: u64, configured: u64 }
+}
+
+impl AngstromSdkError {
+    /// Decodes the revert reason out of an rpc error, if the error is a
+    /// revert.
+    pub(crate) fn from_rpc_revert(error: RpcError<TransportErrorKind>) -> Self {
+        let Some(revert_data) = error
+            .as_error_resp()
+            .and_then(|payload| payload.as_revert_data())
+        else {
+            return Self::EthCall(error);
+        };
+
+        Self::Reverted(
+            decode_revert_reason(&revert_data).unwrap_or_else(|| revert_data.to_string())
+        )
+    }
 }
@@ -137,6 +137,51 @@ impl FillWrapper for () {
     }
 }
 
+/// A filler that can be switched off at runtime.
+#[async_trait::async_trait]
+impl<F: FillWrapper> FillWrapper for Option<F> {
+    type FillOutput = ();
+
+    async fn fill<T>(
+        &self,
+        provider: &AngstromProvider<T>,
+        order: &mut AllOrders
+    ) -> Result<(), FillerError>
+    where
+        T: AngstromOrderApiClient
+    {
+        match self {
+            Some(filler) => filler.fill(provider, order).await,
+            None => Ok(())
+        }
+    }
+
+    async fn fill_many<T>(
+        &self,
+        provider: &AngstromProvider<T>,
+        orders: &mut [AllOrders]
+    ) -> Result<(), FillerError>
+    where
+        T: AngstromOrderApiClient
+    {
+        match self {
+            Some(filler) => filler.fill_many(provider, orders).await,
+            None => Ok(())
+        }
+    }
+
+    async fn prepare<T>(&self, _: &AngstromProvider<T>, _: &AllOrders) -> Result<(), FillerError>
+    where
+        T: AngstromOrderApiClient
+    {
+        Ok(())
+    }
+
+    fn from(&self) -> Option<Address> {
+        self.as_ref().and_then(FillWrapper::from)
+    }
+}
+
 pub trait AngstromFiller: FillWrapper {
     fn wrap_with_filler<F: FillWrapper>(self, filler: F) -> AngstromFillProvider<Self, F> {
         AngstromFillProvider::new(self, filler)
@@ -145,6 +190,8 @@ pub trait AngstromFiller: FillWrapper {
 
 impl AngstromFiller for () {}
 
+impl<F: FillWrapper> AngstromFiller for Option<F> {}
+
 pub(crate) trait FillFrom<F: FillWrapper>: Send + Sync {
     fn prepare_with(self, input_order: &mut AllOrders) -> Result<(), FillerError>;
 }
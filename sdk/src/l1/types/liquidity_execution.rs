@@ -0,0 +1,173 @@
+pub use _position_manager::Transfer as PositionTransfer;
+use alloy_primitives::{Address, Bytes, TxHash, U256};
+use alloy_rpc_types::Log;
+use alloy_sol_types::{SolCall, SolEvent};
+use angstrom_types_primitives::contract_bindings::{
+    pool_manager::PoolManager, position_manager::PositionManager
+};
+
+use crate::l1::{
+    AngstromL1Chain,
+    builders::{LiquidityAction, PositionManagerLiquidity}
+};
+
+mod _position_manager {
+    alloy_sol_types::sol! {
+        /// ERC721 transfer emitted by the PositionManager when a position is
+        /// minted
+        #[derive(Debug, PartialEq, Eq)]
+        event Transfer(address indexed from, address indexed to, uint256 indexed id);
+    }
+}
+
+/// A liquidity call built by `AngstromOrderBuilder`, routed to the contract
+/// that handles it.
+///
+/// PoolManager `unlock` calls are not supported: the PoolManager calls back
+/// into the sender, so they always revert when sent from a wallet.
+#[derive(Debug, Clone)]
+pub enum LiquidityCall {
+    /// `modifyLiquidities` on the PositionManager
+    PositionManager(PositionManager::modifyLiquiditiesCall)
+}
+
+impl LiquidityCall {
+    /// The contract the call is sent to on `chain`.
+    pub fn target(&self, chain: AngstromL1Chain) -> Address {
+        match self {
+            LiquidityCall::PositionManager(_) => {
+                chain.constants().uniswap_constants().position_manager()
+            }
+        }
+    }
+
+    pub fn calldata(&self) -> Bytes {
+        match self {
+            LiquidityCall::PositionManager(call) => call.abi_encode().into()
+        }
+    }
+
+    /// The native ETH that has to be attached to the call.
+    ///
+    /// Only PositionManager mints and increases settle native ETH from
+    /// `msg.value`; the max amount of currency0 is attached for each of them
+    /// when currency0 is native ETH.
+    pub fn native_value(&self) -> U256 {
+        let LiquidityCall::PositionManager(call) = self;
+        let Ok(actions) = PositionManagerLiquidity::decode_unlock_data(&call.unlockData)
+            .and_then(|liquidity| liquidity.decode_actions())
+        else {
+            return U256::ZERO;
+        };
+
+        let settles_eth = actions.iter().any(|action| match action {
+            LiquidityAction::SettlePair(settle) => settle.currency0 == Address::ZERO,
+            LiquidityAction::Sweep(sweep) => sweep.currency == Address::ZERO,
+            _ => false
+        });
+
+        actions
+            .iter()
+            .map(|action| match action {
+                LiquidityAction::MintPosition(mint) if mint.poolKey.currency0 == Address::ZERO => {
+                    U256::from(mint.amount0Max)
+                }
+                LiquidityAction::IncreaseLiquidity(increase) if settles_eth => {
+                    U256::from(increase.amount0Max)
+                }
+                _ => U256::ZERO
+            })
+            .sum()
+    }
+}
+
+impl From<PositionManager::modifyLiquiditiesCall> for LiquidityCall {
+    fn from(value: PositionManager::modifyLiquiditiesCall) -> Self {
+        LiquidityCall::PositionManager(value)
+    }
+}
+
+/// The outcome of a mined liquidity transaction.
+#[derive(Debug, Clone)]
+pub struct LiquidityExecution {
+    pub tx_hash:          TxHash,
+    pub block_number:     Option<u64>,
+    pub gas_used:         u64,
+    pub modify_liquidity: Vec<PoolManager::ModifyLiquidity>,
+    /// The PositionManager token id, if the transaction minted a position
+    pub minted_token_id:  Option<U256>
+}
+
+impl LiquidityExecution {
+    pub(crate) fn from_receipt_logs(
+        tx_hash: TxHash,
+        block_number: Option<u64>,
+        gas_used: u64,
+        pool_manager: Address,
+        position_manager: Address,
+        logs: &[Log]
+    ) -> Self {
+        let modify_liquidity = logs
+            .iter()
+            .filter(|log| log.address() == pool_manager)
+            .filter_map(|log| PoolManager::ModifyLiquidity::decode_log(&log.inner).ok())
+            .map(|log| log.data)
+            .collect();
+
+        let minted_token_id = logs
+            .iter()
+            .filter(|log| log.address() == position_manager)
+            .filter_map(|log| PositionTransfer::decode_log(&log.inner).ok())
+            .find(|log| log.from == Address::ZERO)
+            .map(|log| log.id);
+
+        Self { tx_hash, block_number, gas_used, modify_liquidity, minted_token_id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::aliases::{I24, U24};
+
+    use super::*;
+    use crate::l1::{
+        apis::AngstromOrderBuilder,
+        builders::{_liquidity_calls, MintPosition, PositionManagerLiquidityBuilder}
+    };
+
+    fn mint_call(currency0: Address) -> LiquidityCall {
+        let currency1 = Address::with_last_byte(2);
+        let pool_key = _liquidity_calls::PoolKey {
+            currency0,
+            currency1,
+            fee: U24::from(500),
+            tickSpacing: I24::try_from(10).unwrap(),
+            hooks: Address::ZERO
+        };
+        let mint = _liquidity_calls::mintPositionCall {
+            poolKey:    pool_key,
+            tickLower:  I24::try_from(-100).unwrap(),
+            tickUpper:  I24::try_from(100).unwrap(),
+            liquidity:  U256::from(1000),
+            amount0Max: 5000,
+            amount1Max: 6000,
+            owner:      Address::with_last_byte(3),
+            hookData:   Bytes::default()
+        };
+
+        let mut builder =
+            PositionManagerLiquidityBuilder::<MintPosition>::new(mint).mint_position();
+        builder.add_settle(_liquidity_calls::settlePairCall { currency0, currency1 });
+
+        let mut liquidity = PositionManagerLiquidity::new();
+        liquidity.chain_builder(builder);
+
+        AngstromOrderBuilder::modify_liquidities(liquidity, U256::MAX).into()
+    }
+
+    #[test]
+    fn test_native_value() {
+        assert_eq!(mint_call(Address::ZERO).native_value(), U256::from(5000));
+        assert_eq!(mint_call(Address::with_last_byte(1)).native_value(), U256::ZERO);
+    }
+}
@@ -1,7 +1,19 @@
+mod analytics;
+pub use analytics::*;
+#[cfg(feature = "local-reth")]
+mod bundle_replay;
+#[cfg(feature = "local-reth")]
+pub use bundle_replay::*;
 mod bundle_utils;
 pub use bundle_utils::*;
+mod decoded_bundle;
+pub use decoded_bundle::*;
 mod historical_order_filters;
 pub use historical_order_filters::*;
+mod liquidity_execution;
+pub use liquidity_execution::*;
+mod rewards;
+pub use rewards::*;
 
 pub mod errors;
 pub mod fillers;
@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use alloy_primitives::{TxHash, U256};
+use angstrom_types_primitives::primitive::PoolId;
+use serde::{Deserialize, Serialize};
+use uniswap_storage::v4::utils::mul_div;
+
+use crate::{
+    l1::types::DecodedRewardsUpdate,
+    types::liquidity_amounts::{MAX_TICK, MIN_TICK}
+};
+
+/// Rewards donated to the LPs of a tick range, in token0. The range spans two
+/// neighbouring initialized ticks, so `liquidity` is constant across it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TickRangeReward {
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity:  u128,
+    pub amount:     u128
+}
+
+impl TickRangeReward {
+    /// The share of the reward earned by `position_liquidity` in
+    /// `[tick_lower, tick_upper)`.
+    pub fn position_share(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+        position_liquidity: u128
+    ) -> U256 {
+        if self.liquidity == 0 || self.tick_lower < tick_lower || self.tick_upper > tick_upper {
+            return U256::ZERO;
+        }
+
+        mul_div(U256::from(self.amount), U256::from(position_liquidity), U256::from(self.liquidity))
+    }
+}
+
+/// A pool's reward update in one bundle, resolved to tick ranges.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PoolRewardDistribution {
+    pub pool_id:      PoolId,
+    pub block_number: u64,
+    pub tx_hash:      Option<TxHash>,
+    /// the pool's tick before the bundle
+    pub current_tick: i32,
+    pub ranges:       Vec<TickRangeReward>
+}
+
+impl PoolRewardDistribution {
+    pub fn total(&self) -> u128 {
+        self.ranges.iter().map(|range| range.amount).sum()
+    }
+}
+
+/// The Angstrom rewards earned by a position between two blocks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PositionRewards {
+    pub position_token_id: U256,
+    pub from_block:        u64,
+    pub to_block:          u64,
+    /// token0 earned, from the growth of the position's rewards
+    pub earned:            U256,
+    /// the position's share of each bundle's rewards, from the decoded bundle
+    pub bundles:           Vec<(u64, U256)>
+}
+
+/// Resolves a reward update to the tick ranges it was donated to. `ticks` are
+/// the pool's initialized ticks and their net liquidity before the bundle.
+///
+/// Multi-tick rewards walk from `start_tick` towards `current_tick`, paying
+/// one quantity to the range on the far side of each initialized tick they
+/// cross and the last quantity to the current range.
+pub fn distribute_rewards(
+    update: &DecodedRewardsUpdate,
+    current_tick: i32,
+    ticks: &BTreeMap<i32, i128>
+) -> Vec<TickRangeReward> {
+    let below = |tick: i32| {
+        ticks
+            .range(..tick)
+            .next_back()
+            .map(|(t, _)| *t)
+            .unwrap_or(MIN_TICK)
+    };
+    let above = |tick: i32| {
+        ticks
+            .range(tick + 1..)
+            .next()
+            .map(|(t, _)| *t)
+            .unwrap_or(MAX_TICK)
+    };
+    let current_range = |liquidity: u128, amount: u128| TickRangeReward {
+        tick_lower: below(current_tick + 1),
+        tick_upper: above(current_tick),
+        liquidity,
+        amount
+    };
+
+    let (start_tick, start_liquidity, quantities) = match update {
+        DecodedRewardsUpdate::CurrentOnly { amount: 0, .. } => return Vec::new(),
+        DecodedRewardsUpdate::CurrentOnly { amount, expected_liquidity } => {
+            return vec![current_range(*expected_liquidity, *amount)];
+        }
+        DecodedRewardsUpdate::MultiTick { start_tick, start_liquidity, quantities } => {
+            (start_tick.as_i32(), *start_liquidity, quantities)
+        }
+    };
+
+    let mut ranges = Vec::with_capacity(quantities.len());
+    let mut quantities = quantities.iter().copied();
+    let mut liquidity = start_liquidity;
+
+    if start_tick <= current_tick {
+        for (&tick, &liquidity_net) in ticks.range(start_tick..=current_tick) {
+            let Some(amount) = quantities.next() else { break };
+            ranges.push(TickRangeReward {
+                tick_lower: below(tick),
+                tick_upper: tick,
+                liquidity,
+                amount
+            });
+            liquidity = liquidity.saturating_add_signed(liquidity_net);
+        }
+    } else {
+        for (&tick, &liquidity_net) in ticks.range(current_tick + 1..=start_tick).rev() {
+            let Some(amount) = quantities.next() else { break };
+            ranges.push(TickRangeReward {
+                tick_lower: tick,
+                tick_upper: above(tick),
+                liquidity,
+                amount
+            });
+            liquidity = liquidity.saturating_add_signed(-liquidity_net);
+        }
+    }
+
+    if let Some(amount) = quantities.next() {
+        ranges.push(current_range(liquidity, amount));
+    }
+
+    ranges.retain(|range| range.amount != 0);
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::aliases::I24;
+
+    use super::*;
+
+    fn ticks() -> BTreeMap<i32, i128> {
+        BTreeMap::from([(-200, 100), (-100, 50), (100, -50), (200, -100)])
+    }
+
+    #[test]
+    fn test_distribute_rewards_below() {
+        let update = DecodedRewardsUpdate::MultiTick {
+            start_tick:      I24::unchecked_from(-200),
+            start_liquidity: 0,
+            quantities:      vec![0, 10, 20]
+        };
+
+        let ranges = distribute_rewards(&update, 0, &ticks());
+
+        assert_eq!(
+            ranges,
+            vec![
+                TickRangeReward {
+                    tick_lower: -200,
+                    tick_upper: -100,
+                    liquidity:  100,
+                    amount:     10
+                },
+                TickRangeReward {
+                    tick_lower: -100,
+                    tick_upper: 100,
+                    liquidity:  150,
+                    amount:     20
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_distribute_rewards_above() {
+        let update = DecodedRewardsUpdate::MultiTick {
+            start_tick:      I24::unchecked_from(200),
+            start_liquidity: 0,
+            quantities:      vec![0, 10, 20]
+        };
+
+        let ranges = distribute_rewards(&update, 0, &ticks());
+
+        assert_eq!(
+            ranges,
+            vec![
+                TickRangeReward {
+                    tick_lower: 100,
+                    tick_upper: 200,
+                    liquidity:  100,
+                    amount:     10
+                },
+                TickRangeReward {
+                    tick_lower: -100,
+                    tick_upper: 100,
+                    liquidity:  150,
+                    amount:     20
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_distribute_rewards_current_only() {
+        let update =
+            DecodedRewardsUpdate::CurrentOnly { amount: 30, expected_liquidity: 150 };
+
+        let ranges = distribute_rewards(&update, 0, &ticks());
+
+        assert_eq!(
+            ranges,
+            vec![TickRangeReward {
+                tick_lower: -100,
+                tick_upper: 100,
+                liquidity:  150,
+                amount:     30
+            }]
+        );
+        assert_eq!(ranges[0].position_share(-100, 200, 50), U256::from(10));
+        assert_eq!(ranges[0].position_share(0, 200, 50), U256::ZERO);
+    }
+}
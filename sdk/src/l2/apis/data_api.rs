@@ -1,11 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
+use alloy_consensus::{BlockHeader, Transaction};
 use alloy_eips::BlockId;
-use alloy_network::Network;
-use alloy_primitives::Address;
+use alloy_network::{BlockResponse, Network, TransactionResponse};
+use alloy_primitives::{Address, U256};
 use alloy_sol_types::SolEvent;
 use angstrom_types_primitives::{contract_bindings::pool_manager::PoolManager, primitive::PoolId};
-use futures::TryStreamExt;
+use futures::{StreamExt, TryStreamExt};
 use op_alloy_network::Optimism;
 use uni_v4::{
     BaselinePoolState, L2FeeConfiguration, PoolKey as UniPoolKey,
@@ -30,12 +31,22 @@ use uniswap_storage::{
 };
 
 use crate::{
-    l2::AngstromL2Chain,
+    l2::{
+        AngstromL2Chain,
+        types::{L2HookEvent, L2Swap, L2SwapTaxEstimate}
+    },
     types::{
         common::*,
-        contracts::angstrom_l2::angstrom_l_2_factory::AngstromL2Factory,
+        contracts::angstrom_l2::{
+            angstrom_l_2::AngstromL2, angstrom_l_2_factory::AngstromL2Factory
+        },
+        pool_stats::{self, PoolApr, PoolTvl, rank_by_apr},
         pool_tick_loaders::{DEFAULT_TICKS_PER_BATCH, FullTickLoader, PoolTickDataLoader},
-        utils::historical_pool_manager_modify_liquidity_filter
+        swap_math::swap_exact_in,
+        utils::{
+            historical_contract_events_filter, historical_pool_manager_modify_liquidity_filter,
+            historical_pools_swap_filter
+        }
     }
 };
 
@@ -187,6 +198,156 @@ pub trait AngstromL2DataApi<N: Network>:
             .collect())
     }
 
+    /// Swaps on the angstrom pools, with the taxes and fees charged by their
+    /// hooks and the priority fee of their transactions. A hook event is
+    /// matched to the swap of its pool in the same transaction that is
+    /// closest to it in the logs.
+    async fn historical_swaps(
+        &self,
+        start_block: Option<u64>,
+        end_block: Option<u64>,
+        block_stream_buffer: Option<usize>,
+        chain: AngstromL2Chain
+    ) -> eyre::Result<Vec<WithEthMeta<L2Swap>>> {
+        let hooks = self
+            .all_pool_keys(end_block.map(Into::into).unwrap_or_else(BlockId::latest), chain)
+            .await?
+            .into_iter()
+            .map(|key| (PoolId::from(&key), key.hooks))
+            .collect::<HashMap<_, _>>();
+        if hooks.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let consts = chain.constants();
+        let swap_filters = historical_pools_swap_filter(
+            start_block,
+            end_block,
+            consts.uniswap_constants().pool_manager(),
+            hooks.keys().copied().collect(),
+            consts.angstrom_deploy_block()
+        );
+        let hook_filters = historical_contract_events_filter(
+            start_block,
+            end_block,
+            hooks
+                .values()
+                .copied()
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect(),
+            L2HookEvent::SIGNATURES.to_vec(),
+            consts.angstrom_deploy_block()
+        );
+
+        let (swap_logs, hook_logs) = tokio::try_join!(
+            futures::future::try_join_all(
+                swap_filters
+                    .into_iter()
+                    .map(async move |filter| self.fetch_logs_primitive(&filter).await)
+            ),
+            futures::future::try_join_all(
+                hook_filters
+                    .into_iter()
+                    .map(async move |filter| self.fetch_logs_primitive(&filter).await)
+            )
+        )?;
+
+        let mut swaps = swap_logs
+            .into_iter()
+            .flatten()
+            .filter_map(|log| {
+                let swap = PoolManager::Swap::decode_log(&log.inner).ok()?.data;
+                let hook = *hooks.get(&swap.id)?;
+                Some((
+                    log,
+                    L2Swap { swap, hook, priority_fee_per_gas: None, hook_events: Vec::new() }
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let mut swaps_by_tx = HashMap::<_, Vec<usize>>::new();
+        for (i, (log, _)) in swaps.iter().enumerate() {
+            if let Some(tx_hash) = log.transaction_hash {
+                swaps_by_tx.entry(tx_hash).or_default().push(i);
+            }
+        }
+        for log in hook_logs.into_iter().flatten() {
+            let (Some(tx_hash), Some(log_index), Some(event)) =
+                (log.transaction_hash, log.log_index, L2HookEvent::decode(&log))
+            else {
+                continue;
+            };
+            let closest_swap = swaps_by_tx.get(&tx_hash).and_then(|swap_idxs| {
+                swap_idxs
+                    .iter()
+                    .copied()
+                    .filter(|i| swaps[*i].1.swap.id == event.pool_id())
+                    .min_by_key(|i| {
+                        swaps[*i]
+                            .0
+                            .log_index
+                            .unwrap_or_default()
+                            .abs_diff(log_index)
+                    })
+            });
+            if let Some(i) = closest_swap {
+                swaps[i].1.hook_events.push(event);
+            }
+        }
+
+        let buffer = block_stream_buffer.unwrap_or(100);
+        let (txs, base_fees) = tokio::try_join!(
+            futures::stream::iter(
+                swaps
+                    .iter()
+                    .filter_map(|(log, _)| log.transaction_hash)
+                    .collect::<HashSet<_>>()
+            )
+            .map(async |tx_hash| self.tx_by_hash_primitive(tx_hash).await)
+            .buffer_unordered(buffer)
+            .try_collect::<Vec<_>>(),
+            futures::stream::iter(
+                swaps
+                    .iter()
+                    .filter_map(|(log, _)| log.block_number)
+                    .collect::<HashSet<_>>()
+            )
+            .map(async |block_number| {
+                let block = self
+                    .fetch_block_primitive(block_number.into(), false)
+                    .await?;
+                eyre::Ok((block_number, block.header().base_fee_per_gas()))
+            })
+            .buffer_unordered(buffer)
+            .try_collect::<HashMap<_, _>>()
+        )?;
+        let priority_fees = txs
+            .into_iter()
+            .flatten()
+            .filter_map(|tx| {
+                let base_fee = (*base_fees.get(&tx.block_number()?)?)?;
+                Some((tx.tx_hash(), tx.effective_tip_per_gas(base_fee)?))
+            })
+            .collect::<HashMap<_, _>>();
+
+        Ok(swaps
+            .into_iter()
+            .map(|(log, mut swap)| {
+                swap.priority_fee_per_gas = log
+                    .transaction_hash
+                    .and_then(|tx_hash| priority_fees.get(&tx_hash).copied());
+                WithEthMeta::new(
+                    log.block_number,
+                    log.transaction_hash,
+                    log.transaction_index,
+                    None,
+                    swap
+                )
+            })
+            .collect())
+    }
+
     async fn pool_data_by_pool_id(
         &self,
         pool_id: PoolId,
@@ -303,6 +464,129 @@ pub trait AngstromL2DataApi<N: Network>:
         Ok(pools)
     }
 
+    /// The tokens locked in the pool's liquidity, from the ticks within
+    /// [`INITIAL_TICKS_PER_SIDE`] of the current tick.
+    async fn pool_tvl(
+        &self,
+        pool_id: PoolId,
+        block_id: BlockId,
+        chain: AngstromL2Chain
+    ) -> eyre::Result<PoolTvl> {
+        let pool_key = self.pool_key_by_pool_id(pool_id, block_id, chain).await?;
+
+        let uni_pool_key = UniPoolKey {
+            currency0:   pool_key.currency0,
+            currency1:   pool_key.currency1,
+            fee:         pool_key.fee,
+            tickSpacing: pool_key.tickSpacing,
+            hooks:       pool_key.hooks
+        };
+
+        pool_stats::pool_tvl(
+            self,
+            pool_id,
+            uni_pool_key,
+            chain.constants().uniswap_constants().pool_manager(),
+            block_id
+        )
+        .await
+    }
+
+    /// The realized LP fee APR of every pool over the `window_blocks` blocks
+    /// up to `block_id`, highest first. Swap fees are split between the LPs,
+    /// the pool's creator and the protocol per the pool's fee configuration
+    /// at `block_id`.
+    async fn pool_aprs(
+        &self,
+        window_blocks: u64,
+        block_id: BlockId,
+        chain: AngstromL2Chain
+    ) -> eyre::Result<Vec<PoolApr>> {
+        const FEE_E6: u64 = 1_000_000;
+
+        let end_block = self.block_number_from_block_id(block_id).await?;
+        let start_block = end_block.saturating_sub(window_blocks);
+
+        let pool_keys = self.all_pool_keys(end_block.into(), chain).await?;
+        if pool_keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let consts = chain.constants();
+        let filters = historical_pools_swap_filter(
+            Some(start_block),
+            Some(end_block),
+            consts.uniswap_constants().pool_manager(),
+            pool_keys.iter().map(PoolId::from).collect(),
+            consts.angstrom_deploy_block()
+        );
+
+        let (logs, start_header, end_header) = tokio::try_join!(
+            futures::future::try_join_all(
+                filters
+                    .into_iter()
+                    .map(async move |filter| self.fetch_logs_primitive(&filter).await)
+            ),
+            self.fetch_block_primitive(start_block.into(), false),
+            self.fetch_block_primitive(end_block.into(), false)
+        )?;
+        let window_seconds = end_header
+            .header()
+            .timestamp()
+            .saturating_sub(start_header.header().timestamp());
+
+        let swaps = logs
+            .into_iter()
+            .flatten()
+            .filter_map(|log| PoolManager::Swap::decode_log(&log.inner).ok())
+            .collect::<Vec<_>>();
+
+        let mut aprs = futures::future::try_join_all(pool_keys.into_iter().map(async |key| {
+            let pool_id = PoolId::from(&key);
+            let (tvl, fee_config) = tokio::try_join!(
+                self.pool_tvl(pool_id, end_block.into(), chain),
+                self.fee_configuration_by_pool_id_and_hook(
+                    pool_id,
+                    key.hooks,
+                    end_block.into(),
+                    chain
+                )
+            )?;
+
+            let (mut lp_fees, mut creator_fees, mut protocol_fees) = (0.0, 0.0, 0.0);
+            for swap in swaps.iter().filter(|swap| swap.id == pool_id) {
+                // swap amounts are the swapper's balance deltas, so the input
+                // is negative
+                let value = |fee_e6: u32| {
+                    let amount = if swap.amount0 < 0 {
+                        (U256::from(swap.amount0.unsigned_abs()), U256::ZERO)
+                    } else {
+                        (U256::ZERO, U256::from(swap.amount1.unsigned_abs()))
+                    };
+                    let fee = |amount: U256| amount * U256::from(fee_e6) / U256::from(FEE_E6);
+                    tvl.value_in_token1(fee(amount.0), fee(amount.1))
+                };
+
+                lp_fees += value(swap.fee.to());
+                creator_fees += value(fee_config.creator_swap_fee_e6);
+                protocol_fees += value(fee_config.protocol_swap_fee_e6);
+            }
+
+            Ok::<_, eyre::ErrReport>(PoolApr::new(
+                pool_id,
+                tvl,
+                window_seconds,
+                lp_fees,
+                creator_fees,
+                protocol_fees
+            ))
+        }))
+        .await?;
+        rank_by_apr(&mut aprs);
+
+        Ok(aprs)
+    }
+
     async fn slot0_by_pool_id(
         &self,
         pool_id: PoolId,
@@ -373,16 +657,153 @@ pub trait AngstromL2DataApi<N: Network>:
             withdraw_only: factory_slot0.withdraw_only
         })
     }
+
+    /// The tax, fees and output of an exact input swap of `amount_in` sent
+    /// with `priority_fee_per_gas`, at the pool's state at `block_id`. The
+    /// output is quoted over the ticks around the current price, loading
+    /// more of them until the swap ends within them.
+    async fn estimate_swap_tax(
+        &self,
+        pool_id: PoolId,
+        zero_for_one: bool,
+        amount_in: U256,
+        priority_fee_per_gas: u128,
+        block_id: BlockId,
+        chain: AngstromL2Chain
+    ) -> eyre::Result<L2SwapTaxEstimate> {
+        let pool_key = self.pool_key_by_pool_id(pool_id, block_id, chain).await?;
+        let hook = pool_key.hooks;
+        let priority_fee = U256::from(priority_fee_per_gas);
+
+        let data_deployer_call = GetUniswapV4PoolData::deploy_builder(
+            self.alloy_root_provider().await?,
+            pool_id,
+            chain.constants().uniswap_constants().pool_manager(),
+            pool_key.currency0,
+            pool_key.currency1
+        )
+        .into_transaction_request();
+
+        let (fee_config, swap_tax, jit_tax, out_pool_data) = tokio::try_join!(
+            self.fee_configuration_by_pool_id_and_hook(pool_id, hook, block_id, chain),
+            self.view_call(
+                block_id,
+                hook,
+                AngstromL2::getSwapTaxAmountCall { priorityFee: priority_fee }
+            ),
+            self.view_call(
+                block_id,
+                hook,
+                AngstromL2::getJitTaxAmountCall { priorityFee: priority_fee }
+            ),
+            self.view_deploy_call::<PoolDataV4>(block_id, data_deployer_call)
+        )?;
+        if fee_config.withdraw_only {
+            return Err(eyre::eyre!("hook {hook:?} is in withdraw only mode"));
+        }
+
+        let uni_pool_key = UniPoolKey {
+            currency0:   pool_key.currency0,
+            currency1:   pool_key.currency1,
+            fee:         pool_key.fee,
+            tickSpacing: pool_key.tickSpacing,
+            hooks:       pool_key.hooks
+        };
+        let pool_data: PoolData = (uni_pool_key, out_pool_data).into();
+
+        // widens the loaded ticks until the swap ends within them, or until
+        // there are no more initialized ticks to load
+        let (mut tick_band, mut initialized_ticks) = (INITIAL_TICKS_PER_SIDE, None);
+        loop {
+            let (ticks, _) = self
+                .load_tick_data_in_band(
+                    pool_id,
+                    pool_data.tick.as_i32(),
+                    pool_key.tickSpacing.as_i32(),
+                    block_id,
+                    tick_band,
+                    DEFAULT_TICKS_PER_BATCH,
+                    chain.constants().uniswap_constants().pool_manager()
+                )
+                .await?;
+
+            let mut past_loaded_ticks = false;
+            let estimate = L2SwapTaxEstimate::new(
+                &fee_config,
+                zero_for_one,
+                amount_in,
+                priority_fee_per_gas,
+                swap_tax,
+                jit_tax,
+                |pool_amount_in| {
+                    let swap = swap_exact_in(
+                        pool_data.sqrtPrice.into(),
+                        pool_data.tick.as_i32(),
+                        pool_data.liquidity,
+                        &ticks,
+                        zero_for_one,
+                        pool_amount_in,
+                        fee_config.lp_fee as u32
+                    );
+                    past_loaded_ticks = swap.past_loaded_ticks;
+                    swap.amount_out
+                }
+            )?;
+
+            let loaded_initialized_ticks = ticks.values().filter(|tick| tick.initialized).count();
+            if !past_loaded_ticks || initialized_ticks == Some(loaded_initialized_ticks) {
+                return Ok(estimate);
+            }
+            initialized_ticks = Some(loaded_initialized_ticks);
+            tick_band = tick_band.checked_mul(2).ok_or_else(|| {
+                eyre::eyre!(
+                    "swap of {amount_in} on pool {pool_id:?} crosses too many ticks to load"
+                )
+            })?;
+        }
+    }
 }
 
 #[cfg(test)]
 mod data_api_tests {
 
+    use alloy_network::TransactionBuilder;
+    use alloy_primitives::{Bytes, address};
+    use alloy_provider::Provider;
+    use alloy_sol_types::SolCall;
+
     use super::*;
-    use crate::l2::test_utils::{
-        BASE_USDC, valid_test_params::init_valid_position_params_with_provider
+    use crate::{
+        l2::test_utils::{BASE_USDC, valid_test_params::init_valid_position_params_with_provider},
+        types::providers::primitive_fetcher::PrimitivesFetcher
     };
 
+    /// uniswap's V4Quoter on base
+    const BASE_V4_QUOTER: Address = address!("0x0d5e0f971ed27fbff6c2837bf31316121532048d");
+
+    mod _quoter {
+        alloy_sol_types::sol! {
+            struct PoolKey {
+                address currency0;
+                address currency1;
+                uint24 fee;
+                int24 tickSpacing;
+                address hooks;
+            }
+
+            struct QuoteExactSingleParams {
+                PoolKey poolKey;
+                bool zeroForOne;
+                uint128 exactAmount;
+                bytes hookData;
+            }
+
+            function quoteExactInputSingle(QuoteExactSingleParams memory params)
+                external
+                returns (uint256 amountOut, uint256 gasEstimate);
+        }
+    }
+
     #[tokio::test]
     async fn test_fetch_fee_configuration() {
         let (provider, state) = init_valid_position_params_with_provider().await;
@@ -412,6 +833,100 @@ mod data_api_tests {
         assert_eq!(expected, fee_config);
     }
 
+    #[tokio::test]
+    async fn test_estimate_swap_tax() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+        let amount_in = U256::from(10).pow(U256::from(17));
+
+        let untaxed = provider
+            .estimate_swap_tax(
+                state.pool_id,
+                true,
+                amount_in,
+                0,
+                state.block_number.into(),
+                state.chain
+            )
+            .await
+            .unwrap();
+        let taxed = provider
+            .estimate_swap_tax(
+                state.pool_id,
+                true,
+                amount_in,
+                10_000_000 + 1_000_000,
+                state.block_number.into(),
+                state.chain
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(untaxed.swap_tax, U256::ZERO);
+        assert!(untaxed.amount_out > U256::ZERO);
+        assert!(taxed.swap_tax > U256::ZERO);
+        assert_eq!(taxed.swap_tax, taxed.lp_tax + taxed.creator_tax + taxed.protocol_tax);
+        assert!(taxed.amount_out < untaxed.amount_out);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_swap_tax_matches_quote() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+        let block_id = BlockId::from(state.block_number);
+        let amount_in = 10u128.pow(15);
+        let priority_fee_per_gas = 10_000_000 + 1_000_000;
+
+        let (pool_key, block, estimate) = tokio::try_join!(
+            provider.pool_key_by_pool_id(state.pool_id, block_id, state.chain),
+            provider.fetch_block_primitive(block_id, false),
+            provider.estimate_swap_tax(
+                state.pool_id,
+                true,
+                U256::from(amount_in),
+                priority_fee_per_gas,
+                block_id,
+                state.chain
+            )
+        )
+        .unwrap();
+        let base_fee = block.header().base_fee_per_gas().unwrap_or_default() as u128;
+
+        let quote_call = _quoter::quoteExactInputSingleCall {
+            params: _quoter::QuoteExactSingleParams {
+                poolKey:     _quoter::PoolKey {
+                    currency0:   pool_key.currency0,
+                    currency1:   pool_key.currency1,
+                    fee:         pool_key.fee,
+                    tickSpacing: pool_key.tickSpacing,
+                    hooks:       pool_key.hooks
+                },
+                zeroForOne:  true,
+                exactAmount: amount_in,
+                hookData:    Bytes::new()
+            }
+        };
+        // the hook taxes the swap by the call's priority fee, paid for by the
+        // PoolManager's ETH
+        let mut tx = <Optimism as Network>::TransactionRequest::default();
+        tx.set_from(state.chain.constants().uniswap_constants().pool_manager());
+        tx.set_to(BASE_V4_QUOTER);
+        tx.set_input(quote_call.abi_encode());
+        tx.set_max_priority_fee_per_gas(priority_fee_per_gas);
+        tx.set_max_fee_per_gas(2 * base_fee + priority_fee_per_gas);
+
+        let quote = provider
+            .alloy_root_provider()
+            .await
+            .unwrap()
+            .call(tx)
+            .block(block_id)
+            .await
+            .unwrap();
+        let quote = _quoter::quoteExactInputSingleCall::abi_decode_returns(&quote).unwrap();
+
+        assert!(estimate.swap_tax > U256::ZERO);
+        assert_eq!(estimate.amount_out, quote.amountOut);
+    }
+
     #[tokio::test]
     async fn test_all_token_pairs() {
         let (provider, state) = init_valid_position_params_with_provider().await;
@@ -465,6 +980,31 @@ mod data_api_tests {
         assert_eq!(modify_liquidity.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_historical_swaps() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+
+        let swaps = provider
+            .historical_swaps(
+                Some(state.block_number - 1000),
+                Some(state.block_number),
+                None,
+                state.chain
+            )
+            .await
+            .unwrap();
+
+        assert!(!swaps.is_empty());
+        assert!(swaps.iter().all(|swap| {
+            swap.inner.priority_fee_per_gas.is_some()
+                && swap
+                    .inner
+                    .hook_events
+                    .iter()
+                    .all(|event| event.pool_id() == swap.inner.swap.id)
+        }));
+    }
+
     #[tokio::test]
     async fn test_pool_data_by_pool_id() {
         let (provider, state) = init_valid_position_params_with_provider().await;
@@ -496,6 +1036,37 @@ mod data_api_tests {
         assert_eq!(all_pool_data.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_pool_tvl() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+
+        let tvl = provider
+            .pool_tvl(state.pool_id, state.block_number.into(), state.chain)
+            .await
+            .unwrap();
+
+        assert!(tvl.token0 > 0.0);
+        assert!(tvl.token1 > 0.0);
+        assert!(tvl.total_in_token1() > tvl.token1);
+    }
+
+    #[tokio::test]
+    async fn test_pool_aprs() {
+        let (provider, state) = init_valid_position_params_with_provider().await;
+
+        let aprs = provider
+            .pool_aprs(43200, state.block_number.into(), state.chain)
+            .await
+            .unwrap();
+
+        assert_eq!(aprs.len(), 2);
+        assert!(
+            aprs.iter()
+                .all(|apr| apr.window_seconds > 0 && apr.apr >= 0.0)
+        );
+        assert!(aprs.windows(2).all(|pair| pair[0].apr >= pair[1].apr));
+    }
+
     #[tokio::test]
     async fn test_slot0_by_pool_id() {
         let (provider, state) = init_valid_position_params_with_provider().await;
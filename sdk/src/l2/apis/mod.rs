@@ -1,4 +1,6 @@
 pub(crate) mod data_api;
+pub(crate) mod on_chain;
 pub(crate) mod user_api;
 pub use data_api::AngstromL2DataApi;
+pub use on_chain::{AngstromL2OnChain, AngstromL2OnChainApi};
 pub use user_api::AngstromL2UserApi;
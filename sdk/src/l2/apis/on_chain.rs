@@ -0,0 +1,238 @@
+use std::marker::PhantomData;
+
+use alloy_eips::BlockId;
+use alloy_network::Network;
+use alloy_primitives::{Address, U256, aliases::I24};
+use alloy_provider::Provider;
+use angstrom_types_primitives::{
+    contract_bindings::pool_manager::PoolManager::{self, PoolKey},
+    primitive::PoolId
+};
+use op_alloy_network::Optimism;
+use uni_v4::L2FeeConfiguration;
+use uniswap_storage::v4::{UnpackedPositionInfo, UnpackedSlot0, V4UserLiquidityPosition};
+
+use super::{AngstromL2DataApi, AngstromL2UserApi};
+use crate::{
+    l2::{
+        AngstromL2Chain,
+        types::{L2Swap, L2SwapTaxEstimate}
+    },
+    types::{
+        common::*,
+        contracts::angstrom_l2::angstrom_l_2_factory::AngstromL2Factory,
+        fees::LiquidityPositionFees,
+        pool_stats::{PoolApr, PoolTvl}
+    }
+};
+
+impl<P, N> AngstromL2OnChainApi<N> for P
+where
+    P: AngstromL2DataApi<N>,
+    N: Network
+{
+}
+
+/// Binds an api to one chain, so that its calls do not take a `chain`.
+#[async_trait::async_trait]
+pub trait AngstromL2OnChainApi<N: Network>: AngstromL2DataApi<N> {
+    /// Binds the api to `chain`, failing if the eth provider is connected to
+    /// another chain.
+    async fn on_chain(
+        &self,
+        chain: AngstromL2Chain
+    ) -> eyre::Result<AngstromL2OnChain<'_, Self, N>> {
+        let chain_id = self.alloy_root_provider().await?.get_chain_id().await?;
+        if chain_id != chain.chain_id() {
+            return Err(eyre::eyre!(
+                "eth provider chain {chain_id} does not match angstrom chain {}",
+                chain.chain_id()
+            ));
+        }
+
+        Ok(AngstromL2OnChain::new(self, chain))
+    }
+
+    /// Binds the api to the built-in chain that the eth provider is connected
+    /// to.
+    async fn on_detected_chain(&self) -> eyre::Result<AngstromL2OnChain<'_, Self, N>> {
+        let chain = AngstromL2Chain::from_provider(&self.alloy_root_provider().await?).await?;
+        Ok(AngstromL2OnChain::new(self, chain))
+    }
+
+    /// Binds the api to `chain` without asking the eth provider for its chain
+    /// id, for providers that are not backed by a node.
+    fn on_chain_unchecked(&self, chain: AngstromL2Chain) -> AngstromL2OnChain<'_, Self, N> {
+        AngstromL2OnChain::new(self, chain)
+    }
+}
+
+/// Generates the methods of [`AngstromL2OnChain`], which call the api method
+/// of the same name with the bound chain.
+macro_rules! on_chain_methods {
+    ($($api:ident::$name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {
+        $(
+            #[doc = concat!(
+                "[`", stringify!($api), "::", stringify!($name), "`] on the bound chain."
+            )]
+            pub async fn $name(&self, $($arg: $ty),*) -> eyre::Result<$ret> {
+                $api::<N>::$name(self.provider, $($arg,)* self.chain).await
+            }
+        )*
+    };
+}
+
+/// An api bound to a chain by [`AngstromL2OnChainApi`]. It has the methods of
+/// [`AngstromL2DataApi`] and [`AngstromL2UserApi`], without their `chain`.
+#[derive(Debug)]
+pub struct AngstromL2OnChain<'a, P, N> {
+    provider: &'a P,
+    chain:    AngstromL2Chain,
+    _network: PhantomData<N>
+}
+
+impl<P, N> Clone for AngstromL2OnChain<'_, P, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P, N> Copy for AngstromL2OnChain<'_, P, N> {}
+
+impl<'a, P, N> AngstromL2OnChain<'a, P, N> {
+    fn new(provider: &'a P, chain: AngstromL2Chain) -> Self {
+        Self { provider, chain, _network: PhantomData }
+    }
+
+    pub fn provider(&self) -> &'a P {
+        self.provider
+    }
+
+    pub fn chain(&self) -> AngstromL2Chain {
+        self.chain
+    }
+}
+
+impl<P, N> AngstromL2OnChain<'_, P, N>
+where
+    P: AngstromL2DataApi<N>,
+    N: Network
+{
+    on_chain_methods! {
+        AngstromL2DataApi::all_pool_keys(block_id: BlockId) -> Vec<AngstromL2Factory::PoolKey>;
+        AngstromL2DataApi::all_token_pairs(block_id: BlockId) -> Vec<TokenPair>;
+        AngstromL2DataApi::pool_keys_by_tokens(
+            token0: Address,
+            token1: Address,
+            block_id: BlockId
+        ) -> Vec<AngstromL2Factory::PoolKey>;
+        AngstromL2DataApi::all_tokens(block_id: BlockId) -> Vec<Address>;
+        AngstromL2DataApi::pool_key_by_pool_id(
+            pool_id: PoolId,
+            block_id: BlockId
+        ) -> AngstromL2Factory::PoolKey;
+        AngstromL2DataApi::historical_liquidity_changes(
+            start_block: Option<u64>,
+            end_block: Option<u64>
+        ) -> Vec<WithEthMeta<PoolManager::ModifyLiquidity>>;
+        AngstromL2DataApi::historical_swaps(
+            start_block: Option<u64>,
+            end_block: Option<u64>,
+            block_stream_buffer: Option<usize>
+        ) -> Vec<WithEthMeta<L2Swap>>;
+        AngstromL2DataApi::pool_data_by_pool_id(
+            pool_id: PoolId,
+            load_ticks: bool,
+            block_id: BlockId
+        ) -> (u64, BaselinePoolStateWithKey<Optimism>);
+        AngstromL2DataApi::all_pool_data(
+            load_ticks: bool,
+            block_id: BlockId
+        ) -> Vec<(u64, BaselinePoolStateWithKey<Optimism>)>;
+        AngstromL2DataApi::pool_tvl(pool_id: PoolId, block_id: BlockId) -> PoolTvl;
+        AngstromL2DataApi::pool_aprs(window_blocks: u64, block_id: BlockId) -> Vec<PoolApr>;
+        AngstromL2DataApi::slot0_by_pool_id(pool_id: PoolId, block_id: BlockId) -> UnpackedSlot0;
+        AngstromL2DataApi::hook_by_pool_id(pool_id: PoolId, block_id: BlockId) -> Address;
+        AngstromL2DataApi::fee_configuration_by_pool_id(
+            pool_id: PoolId,
+            block_id: BlockId
+        ) -> L2FeeConfiguration;
+        AngstromL2DataApi::fee_configuration_by_pool_id_and_hook(
+            pool_id: PoolId,
+            hook_address: Address,
+            block_id: BlockId
+        ) -> L2FeeConfiguration;
+        AngstromL2DataApi::estimate_swap_tax(
+            pool_id: PoolId,
+            zero_for_one: bool,
+            amount_in: U256,
+            priority_fee_per_gas: u128,
+            block_id: BlockId
+        ) -> L2SwapTaxEstimate;
+
+        AngstromL2UserApi::position_and_pool_info(
+            position_token_id: U256,
+            block_id: BlockId
+        ) -> (PoolKey, UnpackedPositionInfo);
+        AngstromL2UserApi::position_liquidity(position_token_id: U256, block_id: BlockId) -> u128;
+        AngstromL2UserApi::all_user_positions(
+            owner: Address,
+            start_token_id: U256,
+            end_token_id: U256,
+            pool_id: Option<PoolId>,
+            max_results: Option<usize>,
+            block_id: BlockId
+        ) -> Vec<V4UserLiquidityPosition>;
+        AngstromL2UserApi::user_position_fees(
+            position_token_id: U256,
+            block_id: BlockId
+        ) -> LiquidityPositionFees;
+        AngstromL2UserApi::angstrom_l2_fees(
+            pool_id: PoolId,
+            hook_address: Option<Address>,
+            current_pool_tick: I24,
+            position_token_id: U256,
+            tick_lower: I24,
+            tick_upper: I24,
+            block_id: BlockId
+        ) -> U256;
+        AngstromL2UserApi::build_swap(
+            from: Address,
+            pool_key: AngstromL2Factory::PoolKey,
+            zero_for_one: bool,
+            amount_in: u128,
+            slippage_bps: u16,
+            priority_fee_per_gas: Option<u128>
+        ) -> L2SwapTransaction<N>;
+    }
+}
+
+#[cfg(all(test, not(feature = "local-reth")))]
+mod tests {
+    use super::*;
+    use crate::l2::test_utils::spawn_l2_provider;
+
+    #[tokio::test]
+    async fn test_on_chain() {
+        let (provider, chain) = spawn_l2_provider().await.unwrap();
+        let block_number = provider.provider().get_block_number().await.unwrap();
+
+        let bound = provider.on_chain(chain).await.unwrap();
+        let bound_keys = bound.all_pool_keys(block_number.into()).await.unwrap();
+        let unbound_keys = provider
+            .all_pool_keys(block_number.into(), chain)
+            .await
+            .unwrap();
+        assert_eq!(bound_keys, unbound_keys);
+
+        let detected = provider.on_detected_chain().await.unwrap();
+        assert_eq!(detected.chain().chain_id(), chain.chain_id());
+
+        let other = if chain == AngstromL2Chain::Base {
+            AngstromL2Chain::Unichain
+        } else {
+            AngstromL2Chain::Base
+        };
+        assert!(provider.on_chain(other).await.is_err());
+    }
+}
@@ -1,8 +1,11 @@
 use std::collections::HashSet;
 
+use alloy_consensus::BlockHeader;
 use alloy_eips::BlockId;
-use alloy_network::Network;
+use alloy_network::{BlockResponse, Network, TransactionBuilder};
 use alloy_primitives::{Address, B256, U256, aliases::I24};
+use alloy_provider::Provider;
+use alloy_sol_types::SolCall;
 use angstrom_types_primitives::{
     contract_bindings::pool_manager::PoolManager::PoolKey, primitive::PoolId
 };
@@ -20,8 +23,15 @@ use uniswap_storage::{
 
 use super::data_api::AngstromL2DataApi;
 use crate::{
-    l2::AngstromL2Chain,
-    types::fees::{LiquidityPositionFees, uniswap_fee_deltas}
+    l2::{
+        AngstromL2Chain,
+        builders::L2SwapBuilder,
+        types::{L2SwapRevert, L2SwapTransaction}
+    },
+    types::{
+        contracts::angstrom_l2::angstrom_l_2_factory::AngstromL2Factory,
+        fees::{LiquidityPositionFees, uniswap_fee_deltas}
+    }
 };
 
 impl<P, N> AngstromL2UserApi<N> for P
@@ -273,19 +283,111 @@ pub trait AngstromL2UserApi<N: Network>: AngstromL2DataApi<N> {
 
         Ok(growth_inside - last_growth_inside)
     }
+
+    /// Builds an exact input swap of `amount_in` from `from` through the
+    /// Universal Router and simulates it with `eth_call` at the latest block.
+    ///
+    /// The priority fee defaults to the hook's priority fee tax floor, the
+    /// highest one that is not taxed. The swap reverts if its tax is above the
+    /// estimated one, and `slippage_bps` is taken off the estimated output for
+    /// its minimum output.
+    ///
+    /// An ERC20 input is pulled through Permit2, which `from` has to have
+    /// approved along with the router's allowance on it, see
+    /// [`L2SwapBuilder`]. Without them the simulation reverts with
+    /// [`L2SwapRevert::Permit2Allowance`].
+    async fn build_swap(
+        &self,
+        from: Address,
+        pool_key: AngstromL2Factory::PoolKey,
+        zero_for_one: bool,
+        amount_in: u128,
+        slippage_bps: u16,
+        priority_fee_per_gas: Option<u128>,
+        chain: AngstromL2Chain
+    ) -> eyre::Result<L2SwapTransaction<N>> {
+        let router = chain.universal_router().ok_or_else(|| {
+            eyre::eyre!("no universal router known on chain {}", chain.chain_id())
+        })?;
+        let pool_id = PoolId::from(&pool_key);
+
+        let block_number = self.block_number_from_block_id(BlockId::latest()).await?;
+        let block_id = BlockId::from(block_number);
+        let (block, fee_config) = tokio::try_join!(
+            self.fetch_block_primitive(block_id, false),
+            self.fee_configuration_by_pool_id_and_hook(pool_id, pool_key.hooks, block_id, chain)
+        )?;
+        let priority_fee_per_gas =
+            priority_fee_per_gas.unwrap_or(fee_config.priority_fee_tax_floor as u128);
+
+        let estimate = self
+            .estimate_swap_tax(
+                pool_id,
+                zero_for_one,
+                U256::from(amount_in),
+                priority_fee_per_gas,
+                block_id,
+                chain
+            )
+            .await?;
+        let min_amount_out = u128::try_from(
+            estimate.amount_out * U256::from(10_000 - slippage_bps.min(10_000))
+                / U256::from(10_000)
+        )?;
+
+        let builder = L2SwapBuilder::new(pool_key, zero_for_one, amount_in)
+            .with_amount_out_minimum(min_amount_out)
+            .with_max_swap_tax(estimate.swap_tax);
+        let base_fee = block.header().base_fee_per_gas().unwrap_or_default() as u128;
+
+        let mut tx = N::TransactionRequest::default();
+        tx.set_from(from);
+        tx.set_to(router);
+        tx.set_input(builder.build_execute_call().abi_encode());
+        tx.set_value(builder.native_value());
+        tx.set_max_priority_fee_per_gas(priority_fee_per_gas);
+        tx.set_max_fee_per_gas(2 * base_fee + priority_fee_per_gas);
+
+        let revert = match self
+            .alloy_root_provider()
+            .await?
+            .call(tx.clone())
+            .block(block_id)
+            .await
+        {
+            Ok(_) => None,
+            Err(error) => {
+                let Some(revert_data) = error
+                    .as_error_resp()
+                    .and_then(|payload| payload.as_revert_data())
+                else {
+                    return Err(error.into());
+                };
+                Some(L2SwapRevert::decode(&revert_data))
+            }
+        };
+
+        Ok(L2SwapTransaction { tx, block_number, estimate, min_amount_out, revert })
+    }
 }
 
 #[cfg(test)]
 mod user_api_tests {
 
-    use alloy_primitives::U256;
+    use alloy_eips::BlockId;
+    use alloy_primitives::{Address, B256, U256, keccak256};
+    use alloy_provider::Provider;
+    use alloy_rpc_types::state::{AccountOverride, StateOverride};
+    use alloy_sol_types::SolValue;
 
     use crate::{
         l2::{
-            apis::user_api::AngstromL2UserApi,
-            test_utils::valid_test_params::init_valid_position_params_with_provider
+            apis::{data_api::AngstromL2DataApi, user_api::AngstromL2UserApi},
+            builders::PERMIT2,
+            test_utils::{BASE_USDC, valid_test_params::init_valid_position_params_with_provider},
+            types::L2SwapRevert
         },
-        types::fees::LiquidityPositionFees
+        types::{fees::LiquidityPositionFees, providers::primitive_fetcher::PrimitivesFetcher}
     };
 
     #[tokio::test]
@@ -338,6 +440,85 @@ mod user_api_tests {
         assert_eq!(position_liquidity.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_build_swap() {
+        let (provider, pos_info) = init_valid_position_params_with_provider().await;
+        let pool_key = provider
+            .pool_key_by_pool_id(pos_info.pool_id, BlockId::latest(), pos_info.chain)
+            .await
+            .unwrap();
+        // the PoolManager holds enough ETH to pay for the simulated swap
+        let from = pos_info
+            .chain
+            .constants()
+            .uniswap_constants()
+            .pool_manager();
+
+        let swap = provider
+            .build_swap(from, pool_key, true, 1_000_000_000_000, 50, None, pos_info.chain)
+            .await
+            .unwrap();
+
+        assert_eq!(swap.revert, None);
+        assert_eq!(swap.estimate.swap_tax, U256::ZERO);
+        assert!(swap.min_amount_out > 0);
+        assert!(U256::from(swap.min_amount_out) <= swap.estimate.amount_out);
+    }
+
+    #[tokio::test]
+    async fn test_build_token_input_swap() {
+        let (provider, pos_info) = init_valid_position_params_with_provider().await;
+        let pool_key = provider
+            .pool_key_by_pool_id(pos_info.pool_id, BlockId::latest(), pos_info.chain)
+            .await
+            .unwrap();
+        // the PoolManager holds enough ETH and USDC to pay for the simulated swap
+        let from = pos_info
+            .chain
+            .constants()
+            .uniswap_constants()
+            .pool_manager();
+        let router = pos_info.chain.universal_router().unwrap();
+
+        let swap = provider
+            .build_swap(from, pool_key, false, 1_000_000, 50, None, pos_info.chain)
+            .await
+            .unwrap();
+        assert_eq!(swap.revert, Some(L2SwapRevert::Permit2Allowance));
+
+        // USDC's `allowed` mapping is at slot 10 and Permit2's `allowance`
+        // mapping at slot 1, packing the amount with its expiration
+        let usdc_allowance_slot =
+            keccak256((PERMIT2, keccak256((from, U256::from(10)).abi_encode())).abi_encode());
+        let permit2_allowance_slot = keccak256(
+            (
+                router,
+                keccak256((BASE_USDC, keccak256((from, U256::from(1)).abi_encode())).abi_encode())
+            )
+                .abi_encode()
+        );
+        let overrides = [
+            (BASE_USDC, usdc_allowance_slot, B256::from(U256::MAX)),
+            (PERMIT2, permit2_allowance_slot, B256::from(U256::MAX >> 48))
+        ]
+        .into_iter()
+        .map(|(account, slot, value): (Address, B256, B256)| {
+            let state_diff = [(slot, value)].into_iter().collect();
+            (account, AccountOverride { state_diff: Some(state_diff), ..Default::default() })
+        })
+        .collect::<StateOverride>();
+
+        provider
+            .alloy_root_provider()
+            .await
+            .unwrap()
+            .call(swap.tx)
+            .block(swap.block_number.into())
+            .overrides(overrides)
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn test_user_position_fees() {
         let (provider, pos_info) = init_valid_position_params_with_provider().await;
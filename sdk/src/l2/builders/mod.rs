@@ -0,0 +1,2 @@
+mod swap_builder;
+pub use swap_builder::*;
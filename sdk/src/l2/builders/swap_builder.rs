@@ -0,0 +1,225 @@
+//! Builds exact input swaps on angstrom l2 pools through uniswap's Universal
+//! Router, as a `V4_SWAP` command of a single `SWAP_EXACT_IN_SINGLE` settled
+//! and taken in full.
+//!
+//! An ETH input is sent with the call. An ERC20 input is pulled by the router
+//! through [`PERMIT2`], so before swapping the sender has to approve Permit2
+//! for the token and allow the router to spend it on Permit2 with
+//! `Permit2.approve(token, router, amount, expiration)`. The swap has no
+//! `PERMIT2_PERMIT` command.
+//!
+//! ```ignore
+//! let execute_call = L2SwapBuilder::new(pool_key, true, amount_in)
+//!     .with_amount_out_minimum(min_amount_out)
+//!     .with_max_swap_tax(max_swap_tax)
+//!     .build_execute_call();
+//! ```
+
+use alloy_primitives::{Address, Bytes, U256, address};
+use alloy_sol_types::SolValue;
+
+use crate::types::contracts::angstrom_l2::angstrom_l_2_factory::AngstromL2Factory;
+
+/// Uniswap's Permit2, at the same address on every chain
+pub const PERMIT2: Address = address!("0x000000000022D473030F116dDEE9F6B43aC78BA3");
+
+const V4_SWAP_COMMAND: u8 = 0x10;
+const SWAP_EXACT_IN_SINGLE_ACTION: u8 = 0x06;
+const SETTLE_ALL_ACTION: u8 = 0x0c;
+const TAKE_ALL_ACTION: u8 = 0x0f;
+
+#[derive(Debug, Clone)]
+pub struct L2SwapBuilder {
+    pool_key:           AngstromL2Factory::PoolKey,
+    zero_for_one:       bool,
+    amount_in:          u128,
+    amount_out_minimum: u128,
+    max_swap_tax:       Option<U256>,
+    deadline:           U256
+}
+
+impl L2SwapBuilder {
+    /// A swap of `amount_in` with no minimum output, no max tax and no
+    /// deadline.
+    pub fn new(pool_key: AngstromL2Factory::PoolKey, zero_for_one: bool, amount_in: u128) -> Self {
+        Self {
+            pool_key,
+            zero_for_one,
+            amount_in,
+            amount_out_minimum: 0,
+            max_swap_tax: None,
+            deadline: U256::MAX
+        }
+    }
+
+    pub fn with_amount_out_minimum(mut self, amount_out_minimum: u128) -> Self {
+        self.amount_out_minimum = amount_out_minimum;
+        self
+    }
+
+    /// The hook reverts the swap if its tax, in ETH, is above `max_swap_tax`.
+    pub fn with_max_swap_tax(mut self, max_swap_tax: U256) -> Self {
+        self.max_swap_tax = Some(max_swap_tax);
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: U256) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    pub fn build_execute_call(&self) -> _swap_calls::executeCall {
+        let (currency_in, currency_out) = if self.zero_for_one {
+            (self.pool_key.currency0, self.pool_key.currency1)
+        } else {
+            (self.pool_key.currency1, self.pool_key.currency0)
+        };
+
+        let swap = _swap_calls::ExactInputSingleParams {
+            poolKey:          self.pool_key.clone().into(),
+            zeroForOne:       self.zero_for_one,
+            amountIn:         self.amount_in,
+            amountOutMinimum: self.amount_out_minimum,
+            hookData:         self
+                .max_swap_tax
+                .map(|max_swap_tax| max_swap_tax.abi_encode().into())
+                .unwrap_or_default()
+        };
+        let actions =
+            Bytes::from(vec![SWAP_EXACT_IN_SINGLE_ACTION, SETTLE_ALL_ACTION, TAKE_ALL_ACTION]);
+        let params: Vec<Bytes> = vec![
+            swap.abi_encode().into(),
+            (currency_in, U256::from(self.amount_in))
+                .abi_encode_params()
+                .into(),
+            (currency_out, U256::from(self.amount_out_minimum))
+                .abi_encode_params()
+                .into(),
+        ];
+
+        _swap_calls::executeCall {
+            commands: Bytes::from(vec![V4_SWAP_COMMAND]),
+            inputs:   vec![(actions, params).abi_encode_params().into()],
+            deadline: self.deadline
+        }
+    }
+
+    /// The native ETH that has to be attached to the call, the input when it
+    /// is ETH.
+    pub fn native_value(&self) -> U256 {
+        if self.zero_for_one && self.pool_key.currency0 == Address::ZERO {
+            U256::from(self.amount_in)
+        } else {
+            U256::ZERO
+        }
+    }
+}
+
+pub mod _swap_calls {
+    use alloy_sol_types::sol;
+
+    use crate::types::contracts::angstrom_l2::angstrom_l_2_factory::AngstromL2Factory;
+
+    impl From<AngstromL2Factory::PoolKey> for PoolKey {
+        fn from(value: AngstromL2Factory::PoolKey) -> Self {
+            PoolKey {
+                currency0:   value.currency0,
+                currency1:   value.currency1,
+                fee:         value.fee,
+                tickSpacing: value.tickSpacing,
+                hooks:       value.hooks
+            }
+        }
+    }
+
+    sol! {
+        #[derive(Debug, PartialEq, Eq)]
+        struct PoolKey {
+            address currency0;
+            address currency1;
+            uint24 fee;
+            int24 tickSpacing;
+            address hooks;
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        struct ExactInputSingleParams {
+            PoolKey poolKey;
+            bool zeroForOne;
+            uint128 amountIn;
+            uint128 amountOutMinimum;
+            bytes hookData;
+        }
+
+        #[derive(Debug, PartialEq, Eq)]
+        function execute(bytes calldata commands, bytes[] calldata inputs, uint256 deadline)
+            external
+            payable;
+
+        #[derive(Debug, PartialEq, Eq)]
+        error ExecutionFailed(uint256 commandIndex, bytes message);
+
+        #[derive(Debug, PartialEq, Eq)]
+        error TransactionDeadlinePassed();
+
+        #[derive(Debug, PartialEq, Eq)]
+        error V4TooLittleReceived(uint256 minAmountOutReceived, uint256 amountReceived);
+
+        /// A revert of a hook, wrapped by the PoolManager
+        #[derive(Debug, PartialEq, Eq)]
+        error WrappedError(address target, bytes4 selector, bytes reason, bytes details);
+
+        #[derive(Debug, PartialEq, Eq)]
+        error AllowanceExpired(uint256 deadline);
+
+        #[derive(Debug, PartialEq, Eq)]
+        error InsufficientAllowance(uint256 amount);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::aliases::{I24, U24};
+    use alloy_sol_types::{SolCall, SolValue};
+
+    use super::*;
+
+    fn pool_key() -> AngstromL2Factory::PoolKey {
+        AngstromL2Factory::PoolKey {
+            currency0:   Address::ZERO,
+            currency1:   address!("0x833589fcd6edb6e08f4c7c32d4f71b54bda02913"),
+            fee:         U24::from(160),
+            tickSpacing: I24::try_from(10).unwrap(),
+            hooks:       address!("0x2222222222222222222222222222222222222222")
+        }
+    }
+
+    #[test]
+    fn test_build_execute_call() {
+        let builder = L2SwapBuilder::new(pool_key(), true, 1_000)
+            .with_amount_out_minimum(900)
+            .with_max_swap_tax(U256::from(7));
+        let call = builder.build_execute_call();
+
+        let decoded = _swap_calls::executeCall::abi_decode(&call.abi_encode()).unwrap();
+        assert_eq!(decoded, call);
+        assert_eq!(call.commands, Bytes::from(vec![V4_SWAP_COMMAND]));
+        assert_eq!(call.deadline, U256::MAX);
+
+        let (actions, params) = <(Bytes, Vec<Bytes>)>::abi_decode_params(&call.inputs[0]).unwrap();
+        assert_eq!(actions.len(), params.len());
+
+        let swap = _swap_calls::ExactInputSingleParams::abi_decode(&params[0]).unwrap();
+        assert_eq!(swap.poolKey, pool_key().into());
+        assert_eq!((swap.amountIn, swap.amountOutMinimum), (1_000, 900));
+        assert_eq!(U256::abi_decode(&swap.hookData).unwrap(), U256::from(7));
+
+        let settle = <(Address, U256)>::abi_decode_params(&params[1]).unwrap();
+        let take = <(Address, U256)>::abi_decode_params(&params[2]).unwrap();
+        assert_eq!(settle, (Address::ZERO, U256::from(1_000)));
+        assert_eq!(take, (pool_key().currency1, U256::from(900)));
+
+        assert_eq!(builder.native_value(), U256::from(1_000));
+        assert_eq!(L2SwapBuilder::new(pool_key(), false, 1_000).native_value(), U256::ZERO);
+    }
+}
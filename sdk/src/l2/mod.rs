@@ -1,25 +1,115 @@
 use std::fmt::Debug;
 
+use alloy_network::Network;
+use alloy_primitives::{Address, address};
+use alloy_provider::Provider;
 use uniswap_storage::angstrom::l2::{
     ANGSTROM_L2_CONSTANTS_BASE_MAINNET, ANGSTROM_L2_CONSTANTS_UNICHAIN_MAINNET, AngstromL2Constants
 };
 
 pub mod apis;
+pub mod builders;
+pub mod types;
 
 #[cfg(test)]
 pub(crate) mod test_utils;
 
+/// The chains with a built-in angstrom l2 deployment. Testnets such as Base
+/// Sepolia and Unichain Sepolia have none and are reached with
+/// [`AngstromL2Chain::Custom`].
 #[derive(Debug, Clone, Copy)]
 pub enum AngstromL2Chain {
     Base,
-    Unichain
+    Unichain,
+    /// A deployment that is not built in, such as a devnet or a local anvil.
+    Custom(AngstromL2Constants)
 }
 
 impl AngstromL2Chain {
     pub fn constants(&self) -> AngstromL2Constants {
         match self {
             AngstromL2Chain::Base => ANGSTROM_L2_CONSTANTS_BASE_MAINNET,
-            AngstromL2Chain::Unichain => ANGSTROM_L2_CONSTANTS_UNICHAIN_MAINNET
+            AngstromL2Chain::Unichain => ANGSTROM_L2_CONSTANTS_UNICHAIN_MAINNET,
+            AngstromL2Chain::Custom(constants) => *constants
         }
     }
+
+    pub fn chain_id(&self) -> u64 {
+        self.constants().chain_id()
+    }
+
+    /// Uniswap's Universal Router on the chain, `None` for custom deployments.
+    pub fn universal_router(&self) -> Option<Address> {
+        match self {
+            AngstromL2Chain::Base => Some(address!("0x6ff5693b99212da76ad316178a184ab56d299b43")),
+            AngstromL2Chain::Unichain => {
+                Some(address!("0xef740bf23acae26f6492b10de645d6b98dc8eaf3"))
+            }
+            AngstromL2Chain::Custom(_) => None
+        }
+    }
+
+    /// The built-in chain with the chain id, if any.
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        [AngstromL2Chain::Base, AngstromL2Chain::Unichain]
+            .into_iter()
+            .find(|chain| chain.chain_id() == chain_id)
+    }
+
+    /// The built-in chain that the provider is connected to.
+    pub async fn from_provider<N: Network>(provider: &impl Provider<N>) -> eyre::Result<Self> {
+        let chain_id = provider.get_chain_id().await?;
+        Self::from_chain_id(chain_id)
+            .ok_or_else(|| eyre::eyre!("no angstrom l2 deployment on chain {chain_id}"))
+    }
+}
+
+/// Custom deployments are equal if they are the same angstrom l2 factory on
+/// the same chain.
+impl PartialEq for AngstromL2Chain {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (AngstromL2Chain::Base, AngstromL2Chain::Base)
+            | (AngstromL2Chain::Unichain, AngstromL2Chain::Unichain) => true,
+            (AngstromL2Chain::Custom(a), AngstromL2Chain::Custom(b)) => {
+                a.chain_id() == b.chain_id() && a.angstrom_l2_factory() == b.angstrom_l2_factory()
+            }
+            _ => false
+        }
+    }
+}
+
+impl Eq for AngstromL2Chain {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_chain_id() {
+        assert_eq!(AngstromL2Chain::from_chain_id(8453), Some(AngstromL2Chain::Base));
+        assert_eq!(AngstromL2Chain::from_chain_id(130), Some(AngstromL2Chain::Unichain));
+        assert_eq!(AngstromL2Chain::from_chain_id(1), None);
+    }
+
+    #[test]
+    fn test_custom_chain() {
+        let custom = AngstromL2Chain::Custom(ANGSTROM_L2_CONSTANTS_BASE_MAINNET);
+
+        assert_eq!(custom.chain_id(), AngstromL2Chain::Base.chain_id());
+        assert_eq!(custom, AngstromL2Chain::Custom(ANGSTROM_L2_CONSTANTS_BASE_MAINNET));
+        assert_ne!(custom, AngstromL2Chain::Custom(ANGSTROM_L2_CONSTANTS_UNICHAIN_MAINNET));
+        assert_ne!(custom, AngstromL2Chain::Base);
+    }
+
+    #[cfg(not(feature = "local-reth"))]
+    #[tokio::test]
+    async fn test_from_provider() {
+        let (provider, chain) = test_utils::spawn_l2_provider().await.unwrap();
+
+        let detected = AngstromL2Chain::from_provider(provider.provider())
+            .await
+            .unwrap();
+        assert_eq!(detected.chain_id(), chain.chain_id());
+    }
 }
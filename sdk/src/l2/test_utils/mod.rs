@@ -4,6 +4,7 @@ pub mod valid_test_params;
 
 use alloy_primitives::{Address, address};
 
+use crate::l2::AngstromL2Chain;
 #[cfg(not(feature = "local-reth"))]
 use crate::types::providers::AlloyProviderWrapper;
 
@@ -15,16 +16,31 @@ pub fn base_eth_ws_url() -> String {
     std::env::var("BASE_WS_URL").unwrap_or_else(|_| panic!("BASE_WS_URL not found in .env"))
 }
 
+/// `L2_WS_URL` if set, to run the tests against another l2, otherwise
+/// `BASE_WS_URL`.
+pub fn l2_eth_ws_url() -> String {
+    dotenv::dotenv().ok();
+    std::env::var("L2_WS_URL").unwrap_or_else(|_| base_eth_ws_url())
+}
+
+/// Connects to [`l2_eth_ws_url`], initializing the angstrom addresses for the
+/// chain the provider is on.
 #[cfg(not(feature = "local-reth"))]
 pub async fn eth_provider() -> eyre::Result<AlloyProviderWrapper<op_alloy_network::Optimism>> {
-    use alloy_provider::{Provider, RootProvider, WsConnect};
+    Ok(spawn_l2_provider().await?.0)
+}
+
+#[cfg(not(feature = "local-reth"))]
+pub async fn spawn_l2_provider()
+-> eyre::Result<(AlloyProviderWrapper<op_alloy_network::Optimism>, AngstromL2Chain)> {
+    use alloy_provider::{RootProvider, WsConnect};
     use angstrom_types_primitives::try_init_with_chain_id;
-    use eth_network_exts::{EthNetworkExt, base_mainnet::BaseMainnetExt};
-
-    let _ = try_init_with_chain_id(BaseMainnetExt::<()>::CHAIN_ID);
-    Ok(AlloyProviderWrapper::new(
-        RootProvider::builder()
-            .connect_ws(WsConnect::new(base_eth_ws_url()))
-            .await?
-    ))
+
+    let provider: RootProvider<op_alloy_network::Optimism> = RootProvider::builder()
+        .connect_ws(WsConnect::new(l2_eth_ws_url()))
+        .await?;
+    let chain = AngstromL2Chain::from_provider(&provider).await?;
+    let _ = try_init_with_chain_id(chain.chain_id());
+
+    Ok((AlloyProviderWrapper::new(provider), chain))
 }
@@ -13,7 +13,7 @@ use crate::l2::{ANGSTROM_L2_CONSTANTS_BASE_MAINNET, AngstromL2Chain, test_utils:
 #[cfg(feature = "local-reth")]
 use crate::types::BaseMainnetExt;
 #[cfg(not(feature = "local-reth"))]
-use crate::types::providers::AlloyProviderWrapper;
+use crate::types::providers::NodeOrReplayProvider;
 
 pub struct ValidPositionTestParameters {
     pub owner: Address,
@@ -30,13 +30,24 @@ pub struct ValidPositionTestParameters {
     pub chain: AngstromL2Chain
 }
 
+/// The base provider of the tests, replaying `fixtures/l2.json` when neither
+/// `L2_WS_URL` nor `BASE_WS_URL` is set. See [`NodeOrReplayProvider`] to
+/// record it.
 #[cfg(not(feature = "local-reth"))]
 pub async fn init_valid_position_params_with_provider()
--> (AlloyProviderWrapper<op_alloy_network::Optimism>, ValidPositionTestParameters) {
+-> (NodeOrReplayProvider<op_alloy_network::Optimism>, ValidPositionTestParameters) {
     let params = init_valid_position_params();
-    let provider = crate::l2::test_utils::eth_provider().await.unwrap();
+    dotenv::dotenv().ok();
+    let url = std::env::var("L2_WS_URL")
+        .or_else(|_| std::env::var("BASE_WS_URL"))
+        .ok();
+    let provider = NodeOrReplayProvider::connect_or_replay(url, "l2.json", |_| {
+        crate::l2::test_utils::eth_provider()
+    })
+    .await
+    .unwrap();
 
-    (AlloyProviderWrapper::new(provider), params)
+    (provider, params)
 }
 
 #[cfg(feature = "local-reth")]
@@ -0,0 +1,136 @@
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_types::Log;
+use alloy_sol_types::{SolEvent, SolEventInterface};
+use angstrom_types_primitives::{contract_bindings::pool_manager::PoolManager, primitive::PoolId};
+
+use crate::types::contracts::angstrom_l2::angstrom_l_2::AngstromL2;
+
+/// A tax or fee distributed by an angstrom l2 hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum L2HookEvent {
+    /// MEV tax on a swap, paid to the pool's LPs in ETH
+    LPTax(AngstromL2::LPTaxDistributed),
+    /// MEV tax on a swap, paid to the pool's creator in ETH
+    CreatorTax(AngstromL2::CreatorTaxDistributed),
+    /// MEV tax on a swap, paid to the protocol in ETH
+    ProtocolSwapTax(AngstromL2::ProtocolSwapTaxDistributed),
+    /// tax on JIT liquidity, paid to the protocol in ETH
+    ProtocolJITTax(AngstromL2::ProtocolJITTaxDistributed),
+    /// swap fee paid to the pool's creator
+    CreatorFee(AngstromL2::CreatorFeeDistributed),
+    /// swap fee paid to the protocol
+    ProtocolFee(AngstromL2::ProtocolFeeDistributed)
+}
+
+impl L2HookEvent {
+    pub const SIGNATURES: [B256; 6] = [
+        AngstromL2::LPTaxDistributed::SIGNATURE_HASH,
+        AngstromL2::CreatorTaxDistributed::SIGNATURE_HASH,
+        AngstromL2::ProtocolSwapTaxDistributed::SIGNATURE_HASH,
+        AngstromL2::ProtocolJITTaxDistributed::SIGNATURE_HASH,
+        AngstromL2::CreatorFeeDistributed::SIGNATURE_HASH,
+        AngstromL2::ProtocolFeeDistributed::SIGNATURE_HASH
+    ];
+
+    /// `None` if the log is not one of the hook's tax or fee events.
+    pub fn decode(log: &Log) -> Option<Self> {
+        Some(
+            match AngstromL2::AngstromL2Events::decode_log(&log.inner)
+                .ok()?
+                .data
+            {
+                AngstromL2::AngstromL2Events::LPTaxDistributed(event) => Self::LPTax(event),
+                AngstromL2::AngstromL2Events::CreatorTaxDistributed(event) => {
+                    Self::CreatorTax(event)
+                }
+                AngstromL2::AngstromL2Events::ProtocolSwapTaxDistributed(event) => {
+                    Self::ProtocolSwapTax(event)
+                }
+                AngstromL2::AngstromL2Events::ProtocolJITTaxDistributed(event) => {
+                    Self::ProtocolJITTax(event)
+                }
+                AngstromL2::AngstromL2Events::CreatorFeeDistributed(event) => {
+                    Self::CreatorFee(event)
+                }
+                AngstromL2::AngstromL2Events::ProtocolFeeDistributed(event) => {
+                    Self::ProtocolFee(event)
+                }
+                _ => return None
+            }
+        )
+    }
+
+    pub fn pool_id(&self) -> PoolId {
+        match self {
+            Self::LPTax(event) => event.poolId,
+            Self::CreatorTax(event) => event.poolId,
+            Self::ProtocolSwapTax(event) => event.poolId,
+            Self::ProtocolJITTax(event) => event.poolId,
+            Self::CreatorFee(event) => event.poolId,
+            Self::ProtocolFee(event) => event.poolId
+        }
+    }
+
+    /// The MEV tax on a swap, in ETH. Zero for other events.
+    pub fn swap_tax(&self) -> U256 {
+        match self {
+            Self::LPTax(event) => event.amount,
+            Self::CreatorTax(event) => event.amount,
+            Self::ProtocolSwapTax(event) => event.amount,
+            _ => U256::ZERO
+        }
+    }
+}
+
+/// A swap on an angstrom l2 pool, with the taxes and fees its hook charged in
+/// the same transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct L2Swap {
+    pub swap:                 PoolManager::Swap,
+    pub hook:                 Address,
+    /// the priority fee per gas paid by the swap's transaction, if its block
+    /// has a base fee
+    pub priority_fee_per_gas: Option<u128>,
+    pub hook_events:          Vec<L2HookEvent>
+}
+
+impl L2Swap {
+    /// The MEV tax paid by the swap, in ETH.
+    pub fn tax_paid(&self) -> U256 {
+        self.hook_events.iter().map(L2HookEvent::swap_tax).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Log as PrimitiveLog, address, b256};
+
+    use super::*;
+
+    #[test]
+    fn test_decode_hook_event() {
+        let pool_id = b256!("0x1111111111111111111111111111111111111111111111111111111111111111");
+        let event = AngstromL2::LPTaxDistributed { poolId: pool_id, amount: U256::from(42) };
+        let log = Log {
+            inner: PrimitiveLog {
+                address: address!("0x2222222222222222222222222222222222222222"),
+                data:    event.encode_log_data()
+            },
+            ..Default::default()
+        };
+
+        let decoded = L2HookEvent::decode(&log).unwrap();
+        assert_eq!(decoded, L2HookEvent::LPTax(event));
+        assert_eq!(decoded.pool_id(), pool_id);
+        assert_eq!(decoded.swap_tax(), U256::from(42));
+
+        let unrelated = Log {
+            inner: PrimitiveLog {
+                address: log.inner.address,
+                data:    AngstromL2::WithdrawOnlyModeActivated {}.encode_log_data()
+            },
+            ..Default::default()
+        };
+        assert!(L2HookEvent::decode(&unrelated).is_none());
+    }
+}
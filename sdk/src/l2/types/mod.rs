@@ -0,0 +1,6 @@
+mod hook_events;
+mod swap_tax;
+mod swap_transaction;
+pub use hook_events::*;
+pub use swap_tax::*;
+pub use swap_transaction::*;
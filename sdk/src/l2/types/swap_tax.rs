@@ -0,0 +1,152 @@
+use alloy_primitives::U256;
+use uni_v4::L2FeeConfiguration;
+
+const FACTOR_E6: u32 = 1_000_000;
+
+/// The expected cost of an exact input swap on an angstrom l2 pool sent with
+/// a given priority fee. Taxes are in ETH, swap fees are in the input token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L2SwapTaxEstimate {
+    pub priority_fee_per_gas: u128,
+    /// the MEV tax on the swap, zero at or below the priority fee tax floor
+    pub swap_tax:             U256,
+    pub lp_tax:               U256,
+    pub creator_tax:          U256,
+    pub protocol_tax:         U256,
+    pub creator_swap_fee:     U256,
+    pub protocol_swap_fee:    U256,
+    /// the tax on liquidity added in the same block with the same priority
+    /// fee, zero unless the hook taxes JIT liquidity
+    pub jit_tax:              U256,
+    /// the input that reaches the pool after the tax and swap fees
+    pub pool_amount_in:       U256,
+    /// the output the swapper receives after the tax
+    pub amount_out:           U256
+}
+
+impl L2SwapTaxEstimate {
+    /// Splits the tax and swap fees the way the hook does for an exact input
+    /// swap of `amount_in`. The pool's token0 is ETH, so the tax is taken from
+    /// the input when swapping zero for one and from the output otherwise.
+    /// `swap_pool` returns the pool's output for its input.
+    pub fn new(
+        fee_config: &L2FeeConfiguration,
+        zero_for_one: bool,
+        amount_in: U256,
+        priority_fee_per_gas: u128,
+        swap_tax: U256,
+        jit_tax: U256,
+        swap_pool: impl FnOnce(U256) -> U256
+    ) -> eyre::Result<Self> {
+        let factor = U256::from(FACTOR_E6);
+        let total_swap_fee_e6 = fee_config.creator_swap_fee_e6 + fee_config.protocol_swap_fee_e6;
+
+        let taxed_amount_in = if zero_for_one {
+            amount_in
+                .checked_sub(swap_tax)
+                .ok_or_else(|| eyre::eyre!("swap tax {swap_tax} exceeds the input {amount_in}"))?
+        } else {
+            amount_in
+        };
+        let swap_fee = taxed_amount_in * U256::from(total_swap_fee_e6) / factor;
+        let creator_swap_fee = if total_swap_fee_e6 == 0 {
+            U256::ZERO
+        } else {
+            swap_fee * U256::from(fee_config.creator_swap_fee_e6) / U256::from(total_swap_fee_e6)
+        };
+
+        let pool_amount_in = taxed_amount_in - swap_fee;
+        let pool_amount_out = swap_pool(pool_amount_in);
+        let amount_out = if zero_for_one {
+            pool_amount_out
+        } else {
+            pool_amount_out.checked_sub(swap_tax).ok_or_else(|| {
+                eyre::eyre!("swap tax {swap_tax} exceeds the output {pool_amount_out}")
+            })?
+        };
+
+        let creator_tax = swap_tax * U256::from(fee_config.creator_tax_fee_e6) / factor;
+        let protocol_tax = swap_tax * U256::from(fee_config.protocol_tax_fee_e6) / factor;
+
+        Ok(Self {
+            priority_fee_per_gas,
+            swap_tax,
+            lp_tax: swap_tax - creator_tax - protocol_tax,
+            creator_tax,
+            protocol_tax,
+            creator_swap_fee,
+            protocol_swap_fee: swap_fee - creator_swap_fee,
+            jit_tax,
+            pool_amount_in,
+            amount_out
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fee_config() -> L2FeeConfiguration {
+        L2FeeConfiguration {
+            is_initialized:         true,
+            lp_fee:                 0,
+            creator_tax_fee_e6:     100_000,
+            protocol_tax_fee_e6:    50_000,
+            creator_swap_fee_e6:    1_000,
+            protocol_swap_fee_e6:   3_000,
+            priority_fee_tax_floor: 0,
+            jit_tax_enabled:        false,
+            withdraw_only:          false
+        }
+    }
+
+    #[test]
+    fn test_tax_split() {
+        let estimate = L2SwapTaxEstimate::new(
+            &fee_config(),
+            true,
+            U256::from(1_010_000),
+            1,
+            U256::from(10_000),
+            U256::ZERO,
+            |amount_in| amount_in
+        )
+        .unwrap();
+
+        assert_eq!(estimate.creator_tax, U256::from(1_000));
+        assert_eq!(estimate.protocol_tax, U256::from(500));
+        assert_eq!(estimate.lp_tax, U256::from(8_500));
+        assert_eq!(estimate.creator_swap_fee, U256::from(1_000));
+        assert_eq!(estimate.protocol_swap_fee, U256::from(3_000));
+        assert_eq!(estimate.pool_amount_in, U256::from(996_000));
+        assert_eq!(estimate.amount_out, U256::from(996_000));
+
+        let estimate = L2SwapTaxEstimate::new(
+            &fee_config(),
+            false,
+            U256::from(1_000_000),
+            1,
+            U256::from(10_000),
+            U256::ZERO,
+            |amount_in| amount_in
+        )
+        .unwrap();
+
+        assert_eq!(estimate.pool_amount_in, U256::from(996_000));
+        assert_eq!(estimate.amount_out, U256::from(986_000));
+
+        assert!(
+            L2SwapTaxEstimate::new(
+                &fee_config(),
+                true,
+                U256::from(1_000),
+                1,
+                U256::from(10_000),
+                U256::ZERO,
+                |amount_in| amount_in
+            )
+            .is_err()
+        );
+    }
+}
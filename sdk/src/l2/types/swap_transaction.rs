@@ -0,0 +1,127 @@
+use alloy_network::Network;
+use alloy_primitives::{U256, hex};
+use alloy_sol_types::{SolError, SolInterface, decode_revert_reason};
+
+use super::L2SwapTaxEstimate;
+use crate::{l2::builders::_swap_calls, types::contracts::angstrom_l2::angstrom_l_2::AngstromL2};
+
+/// A swap transaction, simulated at the block it was estimated at.
+#[derive(Debug, Clone)]
+pub struct L2SwapTransaction<N: Network> {
+    pub tx:             N::TransactionRequest,
+    pub block_number:   u64,
+    /// the tax, fees and output the swap is expected to have
+    pub estimate:       L2SwapTaxEstimate,
+    pub min_amount_out: u128,
+    /// why the simulated swap reverted, `None` if it went through
+    pub revert:         Option<L2SwapRevert>
+}
+
+/// Why a swap through the Universal Router reverted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum L2SwapRevert {
+    /// an error of the angstrom hook, such as the swap tax exceeding the max
+    /// or the hook being in withdraw only mode
+    Hook(AngstromL2::AngstromL2Errors),
+    /// the swap's output was below its minimum
+    TooLittleReceived {
+        min_amount_out: U256,
+        amount_out:     U256
+    },
+    DeadlinePassed,
+    /// the router's allowance on Permit2 for the input token is missing, too
+    /// low or expired
+    Permit2Allowance,
+    /// any other revert, as its revert string if it has one
+    Other(String)
+}
+
+impl L2SwapRevert {
+    /// Decodes the revert data of a swap, unwrapping the errors the router
+    /// and the PoolManager wrap hook reverts in.
+    pub fn decode(data: &[u8]) -> Self {
+        if let Ok(error) = _swap_calls::ExecutionFailed::abi_decode(data) {
+            return Self::decode(&error.message);
+        }
+        if let Ok(error) = _swap_calls::WrappedError::abi_decode(data) {
+            return Self::decode(&error.reason);
+        }
+        if let Ok(error) = _swap_calls::V4TooLittleReceived::abi_decode(data) {
+            return Self::TooLittleReceived {
+                min_amount_out: error.minAmountOutReceived,
+                amount_out:     error.amountReceived
+            };
+        }
+        if _swap_calls::TransactionDeadlinePassed::abi_decode(data).is_ok() {
+            return Self::DeadlinePassed;
+        }
+        if _swap_calls::AllowanceExpired::abi_decode(data).is_ok()
+            || _swap_calls::InsufficientAllowance::abi_decode(data).is_ok()
+        {
+            return Self::Permit2Allowance;
+        }
+        if let Ok(error) = AngstromL2::AngstromL2Errors::abi_decode(data) {
+            return Self::Hook(error);
+        }
+
+        Self::Other(decode_revert_reason(data).unwrap_or_else(|| hex::encode_prefixed(data)))
+    }
+}
+
+impl std::fmt::Display for L2SwapRevert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hook(error) => write!(f, "angstrom hook reverted: {error:?}"),
+            Self::TooLittleReceived { min_amount_out, amount_out } => {
+                write!(f, "received {amount_out}, less than the minimum {min_amount_out}")
+            }
+            Self::DeadlinePassed => write!(f, "deadline passed"),
+            Self::Permit2Allowance => write!(f, "missing permit2 allowance for the router"),
+            Self::Other(reason) => write!(f, "{reason}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{Address, FixedBytes};
+
+    use super::*;
+
+    #[test]
+    fn test_decode_swap_revert() {
+        let hook_error = AngstromL2::SwapTaxExceedsSpecifiedMax {}.abi_encode();
+        let wrapped = _swap_calls::WrappedError {
+            target:   Address::with_last_byte(1),
+            selector: FixedBytes::ZERO,
+            reason:   hook_error.into(),
+            details:  Default::default()
+        }
+        .abi_encode();
+
+        assert_eq!(
+            L2SwapRevert::decode(&wrapped),
+            L2SwapRevert::Hook(AngstromL2::AngstromL2Errors::SwapTaxExceedsSpecifiedMax(
+                AngstromL2::SwapTaxExceedsSpecifiedMax {}
+            ))
+        );
+
+        let too_little = _swap_calls::V4TooLittleReceived {
+            minAmountOutReceived: U256::from(10),
+            amountReceived:       U256::from(9)
+        }
+        .abi_encode();
+        assert_eq!(
+            L2SwapRevert::decode(&too_little),
+            L2SwapRevert::TooLittleReceived {
+                min_amount_out: U256::from(10),
+                amount_out:     U256::from(9)
+            }
+        );
+
+        let expired = _swap_calls::AllowanceExpired { deadline: U256::ZERO }.abi_encode();
+        assert_eq!(L2SwapRevert::decode(&expired), L2SwapRevert::Permit2Allowance);
+
+        assert!(matches!(L2SwapRevert::decode(&[1, 2, 3]), L2SwapRevert::Other(_)));
+    }
+}
@@ -4,6 +4,8 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::wrong_self_convention)]
 
+#[cfg(all(feature = "l1", feature = "l2"))]
+pub mod any_chain;
 #[cfg(feature = "l1")]
 pub mod l1;
 #[cfg(feature = "l2")]
@@ -231,3 +231,27 @@ impl OrderFrom for AllOrders {
             .unwrap_or_default()
     }
 }
+
+/// The width of an analytics bucket, either in blocks or in seconds of block
+/// timestamp.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum BucketInterval {
+    Blocks(u64),
+    Seconds(u64)
+}
+
+impl BucketInterval {
+    /// Whether block timestamps are needed to bucket blocks.
+    pub fn needs_timestamps(&self) -> bool {
+        matches!(self, BucketInterval::Seconds(_))
+    }
+
+    /// The start of the bucket containing the block, as a block number or a
+    /// timestamp.
+    pub fn bucket_start(&self, block_number: u64, block_timestamp: u64) -> u64 {
+        match *self {
+            BucketInterval::Blocks(width) => block_number - block_number % width.max(1),
+            BucketInterval::Seconds(width) => block_timestamp - block_timestamp % width.max(1)
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use alloy_primitives::{U256, uint};
+use uniswap_storage::v4::utils::mul_div;
+
+pub const MIN_TICK: i32 = -887272;
+pub const MAX_TICK: i32 = 887272;
+
+pub(crate) const Q96: U256 = uint!(0x1000000000000000000000000_U256);
+
+/// `sqrt(1.0001^tick) * 2^96`, rounded up, as computed by uniswap's TickMath.
+pub fn sqrt_price_at_tick(tick: i32) -> U256 {
+    const STEPS: [(u32, U256); 19] = [
+        (0x2, uint!(0xfff97272373d413259a46990580e213a_U256)),
+        (0x4, uint!(0xfff2e50f5f656932ef12357cf3c7fdcc_U256)),
+        (0x8, uint!(0xffe5caca7e10e4e61c3624eaa0941cd0_U256)),
+        (0x10, uint!(0xffcb9843d60f6159c9db58835c926644_U256)),
+        (0x20, uint!(0xff973b41fa98c081472e6896dfb254c0_U256)),
+        (0x40, uint!(0xff2ea16466c96a3843ec78b326b52861_U256)),
+        (0x80, uint!(0xfe5dee046a99a2a811c461f1969c3053_U256)),
+        (0x100, uint!(0xfcbe86c7900a88aedcffc83b479aa3a4_U256)),
+        (0x200, uint!(0xf987a7253ac413176f2b074cf7815e54_U256)),
+        (0x400, uint!(0xf3392b0822b70005940c7a398e4b70f3_U256)),
+        (0x800, uint!(0xe7159475a2c29b7443b29c7fa6e889d9_U256)),
+        (0x1000, uint!(0xd097f3bdfd2022b8845ad8f792aa5825_U256)),
+        (0x2000, uint!(0xa9f746462d870fdf8a65dc1f90e061e5_U256)),
+        (0x4000, uint!(0x70d869a156d2a1b890bb3df62baf32f7_U256)),
+        (0x8000, uint!(0x31be135f97d08fd981231505542fcfa6_U256)),
+        (0x10000, uint!(0x9aa508b5b7a84e1c677de54f3e99bc9_U256)),
+        (0x20000, uint!(0x5d6af8dedb81196699c329225ee604_U256)),
+        (0x40000, uint!(0x2216e584f5fa1ea926041bedfe98_U256)),
+        (0x80000, uint!(0x48a170391f7dc42444e8fa2_U256))
+    ];
+
+    let tick = tick.clamp(MIN_TICK, MAX_TICK);
+    let abs_tick = tick.unsigned_abs();
+
+    let mut ratio = if abs_tick & 0x1 != 0 {
+        uint!(0xfffcb933bd6fad37aa2d162d1a594001_U256)
+    } else {
+        U256::from(1) << 128
+    };
+    for (bit, factor) in STEPS {
+        if abs_tick & bit != 0 {
+            ratio = (ratio * factor) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    let rounding =
+        if (ratio & U256::from(u32::MAX)).is_zero() { U256::ZERO } else { U256::from(1) };
+    (ratio >> 32) + rounding
+}
+
+/// The token0 and token1 that `liquidity` between the ticks is worth at
+/// `sqrt_price_x96`, rounded down, as computed by uniswap's LiquidityAmounts.
+pub fn amounts_for_liquidity(
+    sqrt_price_x96: U256,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128
+) -> (U256, U256) {
+    let sqrt_lower = sqrt_price_at_tick(tick_lower);
+    let sqrt_upper = sqrt_price_at_tick(tick_upper);
+
+    if sqrt_price_x96 <= sqrt_lower {
+        (amount0_for_liquidity(sqrt_lower, sqrt_upper, liquidity), U256::ZERO)
+    } else if sqrt_price_x96 < sqrt_upper {
+        (
+            amount0_for_liquidity(sqrt_price_x96, sqrt_upper, liquidity),
+            amount1_for_liquidity(sqrt_lower, sqrt_price_x96, liquidity)
+        )
+    } else {
+        (U256::ZERO, amount1_for_liquidity(sqrt_lower, sqrt_upper, liquidity))
+    }
+}
+
+fn amount0_for_liquidity(sqrt_lower: U256, sqrt_upper: U256, liquidity: u128) -> U256 {
+    mul_div(U256::from(liquidity) << 96, sqrt_upper - sqrt_lower, sqrt_upper) / sqrt_lower
+}
+
+fn amount1_for_liquidity(sqrt_lower: U256, sqrt_upper: U256, liquidity: u128) -> U256 {
+    mul_div(U256::from(liquidity), sqrt_upper - sqrt_lower, Q96)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqrt_price_at_tick() {
+        assert_eq!(sqrt_price_at_tick(0), Q96);
+        assert_eq!(sqrt_price_at_tick(MIN_TICK), U256::from(4295128739u64));
+        assert_eq!(
+            sqrt_price_at_tick(MAX_TICK),
+            uint!(1461446703485210103287273052203988822378723970342_U256)
+        );
+        assert!(sqrt_price_at_tick(-1) < Q96 && sqrt_price_at_tick(1) > Q96);
+    }
+
+    #[test]
+    fn test_amounts_for_liquidity() {
+        let liquidity = 1_000_000_000_000u128;
+
+        let (below0, below1) =
+            amounts_for_liquidity(sqrt_price_at_tick(-200), -100, 100, liquidity);
+        assert!(below0 > U256::ZERO && below1.is_zero());
+
+        let (above0, above1) = amounts_for_liquidity(sqrt_price_at_tick(200), -100, 100, liquidity);
+        assert!(above0.is_zero() && above1 > U256::ZERO);
+
+        // at a price of 1 a symmetric range holds the same of each token
+        let (in0, in1) = amounts_for_liquidity(Q96, -100, 100, liquidity);
+        assert!(in0 > U256::ZERO && in0.abs_diff(in1) <= U256::from(1));
+        assert!(in0 < below0 && in1 < above1);
+    }
+}
@@ -9,5 +9,8 @@ pub use eth_network_exts::{base_mainnet::BaseMainnetExt, unichain_mainnet::Unich
 
 pub mod common;
 pub mod fees;
+pub mod liquidity_amounts;
+pub mod pool_stats;
+pub mod swap_math;
 
 pub mod contracts;
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use alloy_eips::BlockId;
+use alloy_network::Network;
+use alloy_primitives::{Address, U256};
+use angstrom_types_primitives::primitive::PoolId;
+use serde::{Deserialize, Serialize};
+use uni_v4::{
+    PoolKey as UniPoolKey,
+    baseline_pool_factory::INITIAL_TICKS_PER_SIDE,
+    bindings::get_uniswap_v_4_pool_data::GetUniswapV4PoolData,
+    pool_data_loader::{PoolData, PoolDataV4},
+    tick_info::TickInfo
+};
+
+use crate::types::pool_tick_loaders::{
+    DEFAULT_TICKS_PER_BATCH, FullTickLoader, PoolTickDataLoader
+};
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0;
+
+/// The tokens locked in a pool's liquidity. Amounts are in whole tokens and
+/// the price is token1 per token0, both adjusted for the tokens' decimals.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct PoolTvl {
+    pub token0:       f64,
+    pub token1:       f64,
+    pub price_1over0: f64,
+    pub decimals0:    u8,
+    pub decimals1:    u8
+}
+
+impl PoolTvl {
+    /// Walks the initialized ticks outwards from the current price, summing
+    /// the token0 locked above it and the token1 locked below it. Liquidity
+    /// outside of the loaded ticks is not counted.
+    pub fn from_ticks(
+        ticks: &HashMap<i32, TickInfo>,
+        current_tick: i32,
+        sqrt_price_x96: U256,
+        liquidity: u128,
+        decimals0: u8,
+        decimals1: u8
+    ) -> Self {
+        let sqrt_price = f64::from(sqrt_price_x96) / 2f64.powi(96);
+        let sqrt_price_at = |tick: i32| 1.0001f64.powf(tick as f64 / 2.0);
+
+        let mut sorted_ticks = ticks
+            .iter()
+            .filter(|(_, info)| info.initialized)
+            .map(|(tick, info)| (*tick, info.liquidity_net as f64))
+            .collect::<Vec<_>>();
+        sorted_ticks.sort_by_key(|(tick, _)| *tick);
+
+        let mut amount0 = 0.0;
+        let (mut range_liquidity, mut lower) = (liquidity as f64, sqrt_price);
+        for &(tick, liquidity_net) in sorted_ticks.iter().filter(|(t, _)| *t > current_tick) {
+            let upper = sqrt_price_at(tick);
+            amount0 += range_liquidity.max(0.0) * (1.0 / lower - 1.0 / upper);
+            range_liquidity += liquidity_net;
+            lower = upper;
+        }
+
+        let mut amount1 = 0.0;
+        let (mut range_liquidity, mut upper) = (liquidity as f64, sqrt_price);
+        for &(tick, liquidity_net) in sorted_ticks
+            .iter()
+            .rev()
+            .filter(|(t, _)| *t <= current_tick)
+        {
+            let lower = sqrt_price_at(tick);
+            amount1 += range_liquidity.max(0.0) * (upper - lower);
+            range_liquidity -= liquidity_net;
+            upper = lower;
+        }
+
+        Self {
+            token0: amount0 / 10f64.powi(decimals0 as i32),
+            token1: amount1 / 10f64.powi(decimals1 as i32),
+            price_1over0: sqrt_price * sqrt_price * 10f64.powi(decimals0 as i32 - decimals1 as i32),
+            decimals0,
+            decimals1
+        }
+    }
+
+    /// The total value locked, in token1.
+    pub fn total_in_token1(&self) -> f64 {
+        self.token1 + self.token0 * self.price_1over0
+    }
+
+    /// The value of raw token amounts, in token1.
+    pub fn value_in_token1(&self, amount0: U256, amount1: U256) -> f64 {
+        f64::from(amount1) / 10f64.powi(self.decimals1 as i32)
+            + f64::from(amount0) / 10f64.powi(self.decimals0 as i32) * self.price_1over0
+    }
+}
+
+/// The tokens locked in the pool's liquidity, from the ticks within
+/// [`INITIAL_TICKS_PER_SIDE`] of the current tick. `pool_key` carries the
+/// pool's LP fee.
+pub(crate) async fn pool_tvl<N, P>(
+    provider: &P,
+    pool_id: PoolId,
+    pool_key: UniPoolKey,
+    pool_manager: Address,
+    block_id: BlockId
+) -> eyre::Result<PoolTvl>
+where
+    N: Network,
+    P: PoolTickDataLoader<N>
+{
+    let data_deployer_call = GetUniswapV4PoolData::deploy_builder(
+        provider.alloy_root_provider().await?,
+        pool_id,
+        pool_manager,
+        pool_key.currency0,
+        pool_key.currency1
+    )
+    .into_transaction_request();
+
+    let out_pool_data = provider
+        .view_deploy_call::<PoolDataV4>(block_id, data_deployer_call)
+        .await?;
+    let pool_data: PoolData = (pool_key, out_pool_data).into();
+
+    let (ticks, _) = provider
+        .load_tick_data_in_band(
+            pool_id,
+            pool_data.tick.as_i32(),
+            pool_key.tickSpacing.as_i32(),
+            block_id,
+            INITIAL_TICKS_PER_SIDE,
+            DEFAULT_TICKS_PER_BATCH,
+            pool_manager
+        )
+        .await?;
+
+    Ok(PoolTvl::from_ticks(
+        &ticks,
+        pool_data.tick.as_i32(),
+        pool_data.sqrtPrice.into(),
+        pool_data.liquidity,
+        pool_data.tokenADecimals,
+        pool_data.tokenBDecimals
+    ))
+}
+
+/// A pool's realized LP fee APR over a trailing window. Fees are valued in
+/// token1 at the pool's current price.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct PoolApr {
+    pub pool_id:        PoolId,
+    pub tvl:            PoolTvl,
+    pub window_seconds: u64,
+    /// fees paid to the pool's LPs
+    pub lp_fees:        f64,
+    /// fees taken by the pool's creator (L2 only)
+    pub creator_fees:   f64,
+    pub protocol_fees:  f64,
+    /// the LP fees over the window, annualized, as a fraction of the TVL
+    pub apr:            f64
+}
+
+impl PoolApr {
+    pub fn new(
+        pool_id: PoolId,
+        tvl: PoolTvl,
+        window_seconds: u64,
+        lp_fees: f64,
+        creator_fees: f64,
+        protocol_fees: f64
+    ) -> Self {
+        let total_value = tvl.total_in_token1();
+        let apr = if total_value > 0.0 && window_seconds > 0 {
+            lp_fees / total_value * SECONDS_PER_YEAR / window_seconds as f64
+        } else {
+            0.0
+        };
+
+        Self { pool_id, tvl, window_seconds, lp_fees, creator_fees, protocol_fees, apr }
+    }
+}
+
+/// Orders pools by APR, highest first.
+pub fn rank_by_apr(aprs: &mut [PoolApr]) {
+    aprs.sort_by(|a, b| b.apr.total_cmp(&a.apr));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(liquidity_net: i128) -> TickInfo {
+        TickInfo { initialized: true, liquidity_net, liquidity_gross: liquidity_net.unsigned_abs() }
+    }
+
+    #[test]
+    fn test_tvl_from_ticks() {
+        let liquidity = 1_000_000_000_000_000_000u128;
+        let ticks = HashMap::from([
+            (-600, tick(liquidity as i128)),
+            (600, tick(-(liquidity as i128))),
+            (1200, tick(0))
+        ]);
+
+        let tvl = PoolTvl::from_ticks(&ticks, 0, U256::from(1) << 96, liquidity, 18, 18);
+
+        let expected = 1.0 - 1.0001f64.powi(-300);
+        assert!((tvl.token0 - expected).abs() < 1e-9);
+        assert!((tvl.token1 - expected).abs() < 1e-9);
+        assert!((tvl.price_1over0 - 1.0).abs() < 1e-12);
+        assert!((tvl.total_in_token1() - 2.0 * expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_apr() {
+        let tvl = PoolTvl {
+            token0:       0.0,
+            token1:       1000.0,
+            price_1over0: 1.0,
+            decimals0:    6,
+            decimals1:    6
+        };
+        let lp_fees = tvl.value_in_token1(U256::from(500_000), U256::from(500_000));
+
+        let apr = PoolApr::new(PoolId::ZERO, tvl, 86400, lp_fees, 0.0, 0.0);
+
+        assert!((apr.apr - 0.365).abs() < 1e-9);
+    }
+}
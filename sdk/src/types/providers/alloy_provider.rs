@@ -1,18 +1,21 @@
-use std::ops::Deref;
+use std::{ops::Deref, sync::Arc};
 
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockId;
-use alloy_json_rpc::RpcError;
 use alloy_network::{BlockResponse, Ethereum, Network, ReceiptResponse, TransactionBuilder};
-use alloy_primitives::{Address, StorageKey, StorageValue, TxHash};
+use alloy_primitives::{Address, Bytes, StorageKey, StorageValue, TxHash};
 use alloy_provider::{DynProvider, Provider, RootProvider};
-use alloy_rpc_types::{BlockTransactionsKind, Filter, Log};
+use alloy_rpc_types::{AccountInfo, BlockTransactionsKind, Filter, Log};
 use alloy_sol_types::{SolCall, SolType};
 use eyre::Context;
 use uniswap_storage::StorageSlotFetcher;
 
 use crate::types::{
-    providers::primitive_fetcher::PrimitivesFetcher, utils::split_filter_by_blocks
+    providers::{
+        primitive_fetcher::PrimitivesFetcher,
+        retry::{RateLimiter, RetryPolicy, is_range_limit_error}
+    },
+    utils::split_filter_by_blocks
 };
 
 /// Wrapper for alloy providers that implements SDK traits.
@@ -20,12 +23,37 @@ use crate::types::{
 /// `RethDbProviderWrapper`.
 #[derive(Debug, Clone)]
 pub struct AlloyProviderWrapper<N: Network = Ethereum> {
-    provider: DynProvider<N>
+    provider:     DynProvider<N>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>
 }
 
 impl<N: Network> AlloyProviderWrapper<N> {
     pub fn new(provider: impl Provider<N> + 'static) -> Self {
-        Self { provider: DynProvider::new(provider) }
+        Self {
+            provider:     DynProvider::new(provider),
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None
+        }
+    }
+
+    /// Retries storage reads, calls, blocks and logs that fail with a
+    /// transient error such as a rate limit or a timeout.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Limits storage reads, calls, blocks and logs to `requests_per_second`,
+    /// allowing bursts of up to `burst` requests. Clones of the wrapper share
+    /// the limit.
+    pub fn with_rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(requests_per_second, burst)));
+        self
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
     }
 
     pub fn provider(&self) -> &DynProvider<N> {
@@ -35,6 +63,34 @@ impl<N: Network> AlloyProviderWrapper<N> {
     pub fn into_inner(self) -> DynProvider<N> {
         self.provider
     }
+
+    /// Runs a JSON-RPC batch of `requests` requests under the retry policy
+    /// and rate limit.
+    pub(crate) async fn retry_batch<T, E, F, Fut>(&self, requests: u32, request: F) -> Result<T, E>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+        E: std::fmt::Debug
+    {
+        self.retry_policy
+            .retry_batch(self.rate_limiter.as_deref(), requests, request)
+            .await
+    }
+
+    /// Calls `tx` at `block_id`, retried and rate limited like the other
+    /// reads.
+    pub(crate) async fn call_at(
+        &self,
+        tx: N::TransactionRequest,
+        block_id: BlockId
+    ) -> eyre::Result<Bytes> {
+        Ok(self
+            .retry_policy
+            .retry(self.rate_limiter.as_deref(), || {
+                self.call(tx.clone()).block(block_id).into_future()
+            })
+            .await?)
+    }
 }
 
 impl<N: Network> Deref for AlloyProviderWrapper<N> {
@@ -60,9 +116,13 @@ impl<N: Network> StorageSlotFetcher for AlloyProviderWrapper<N> {
         block_id: BlockId
     ) -> eyre::Result<StorageValue> {
         Ok(self
-            .root()
-            .get_storage_at(address, key.into())
-            .block_id(block_id)
+            .retry_policy
+            .retry(self.rate_limiter.as_deref(), || {
+                self.root()
+                    .get_storage_at(address, key.into())
+                    .block_id(block_id)
+                    .into_future()
+            })
             .await?)
     }
 }
@@ -73,32 +133,28 @@ where
     DynProvider<N>: Provider<N>
 {
     async fn fetch_logs_primitive(&self, filter: &Filter) -> eyre::Result<Vec<Log>> {
-        let logs_err = match self.provider.get_logs(filter).await {
+        let logs_err = match self
+            .retry_policy
+            .retry(self.rate_limiter.as_deref(), || self.provider.get_logs(filter))
+            .await
+        {
             Ok(v) => return Ok(v),
             Err(e) => e
         };
 
-        match &logs_err {
-            RpcError::ErrorResp(error_payload) => {
-                if (error_payload.message.contains("query exceeds max results")
-                    || error_payload
-                        .to_string()
-                        .contains("query exceeds max results"))
-                    && let Some((filter_a, filter_b)) = split_filter_by_blocks(filter)
-                {
-                    let (logs_a, logs_b) = tokio::try_join!(
-                        self.fetch_logs_primitive(&filter_a),
-                        self.fetch_logs_primitive(&filter_b)
-                    )?;
-
-                    let logs = logs_a.into_iter().chain(logs_b).collect();
-                    return Ok(logs);
-                }
-
-                return Err(eyre::eyre!("{logs_err:?}"));
-            }
-            e => return Err(eyre::eyre!("{e:?}"))
+        if is_range_limit_error(&format!("{logs_err:?}"))
+            && let Some((filter_a, filter_b)) = split_filter_by_blocks(filter)
+        {
+            let (logs_a, logs_b) = tokio::try_join!(
+                self.fetch_logs_primitive(&filter_a),
+                self.fetch_logs_primitive(&filter_b)
+            )?;
+
+            let logs = logs_a.into_iter().chain(logs_b).collect();
+            return Ok(logs);
         }
+
+        Err(eyre::eyre!("{logs_err:?}"))
     }
 
     async fn view_call<IC>(
@@ -114,7 +170,7 @@ where
         tx.set_to(contract);
         tx.set_input(call.abi_encode());
 
-        let data = self.call(tx).block(block_id).await?;
+        let data = self.call_at(tx, block_id).await?;
         Ok(IC::abi_decode_returns(&data).wrap_err(format!(
             "block_id: {block_id:?}, contract: {contract:?}, call: {call:?}\noutput: {data:?}"
         ))?)
@@ -128,10 +184,32 @@ where
     where
         IC: SolType + Send
     {
-        let data = self.call(tx).block(block_id).await?;
+        let data = self.call_at(tx, block_id).await?;
         Ok(IC::abi_decode(&data)?)
     }
 
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<AccountInfo> {
+        let (balance, nonce, code) = tokio::try_join!(
+            self.retry_policy.retry(self.rate_limiter.as_deref(), || {
+                self.get_balance(address).block_id(block_id).into_future()
+            }),
+            self.retry_policy.retry(self.rate_limiter.as_deref(), || {
+                self.get_transaction_count(address)
+                    .block_id(block_id)
+                    .into_future()
+            }),
+            self.retry_policy.retry(self.rate_limiter.as_deref(), || {
+                self.get_code_at(address).block_id(block_id).into_future()
+            })
+        )?;
+
+        Ok(AccountInfo { balance, nonce, code })
+    }
+
     async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<N>> {
         Ok(self.provider.root().clone())
     }
@@ -140,7 +218,8 @@ where
         let number = if let Some(b) = block_id.as_u64() {
             b
         } else {
-            self.get_block(block_id)
+            self.retry_policy
+                .retry(self.rate_limiter.as_deref(), || self.get_block(block_id).into_future())
                 .await?
                 .ok_or_else(|| eyre::eyre!("block not found: {block_id:?}"))?
                 .header()
@@ -157,8 +236,10 @@ where
     ) -> eyre::Result<<N as Network>::BlockResponse> {
         let tx_kind =
             if full { BlockTransactionsKind::Full } else { BlockTransactionsKind::Hashes };
-        self.get_block(block_id)
-            .kind(tx_kind)
+        self.retry_policy
+            .retry(self.rate_limiter.as_deref(), || {
+                self.get_block(block_id).kind(tx_kind).into_future()
+            })
             .await?
             .ok_or_else(|| eyre::eyre!("block does not exist: {block_id:?}"))
     }
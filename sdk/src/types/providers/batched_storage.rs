@@ -0,0 +1,294 @@
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering}
+    },
+    time::Duration
+};
+
+use alloy_eips::BlockId;
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::{Address, StorageKey, StorageValue, TxHash, U256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::{AccountInfo, Filter, Log};
+use alloy_sol_types::{SolCall, SolType};
+use futures::future::join_all;
+use tokio::sync::oneshot;
+use uniswap_storage::StorageSlotFetcher;
+
+use crate::types::providers::{
+    AlloyProviderWrapper, primitive_fetcher::PrimitivesFetcher, retry::is_transient_error
+};
+
+const DEFAULT_WINDOW: Duration = Duration::from_millis(5);
+const DEFAULT_MAX_BATCH_SIZE: usize = 100;
+
+/// A [`StorageSlotFetcher`] that gathers the slots requested for the same
+/// block within a short window and reads them with one JSON-RPC batch of
+/// `eth_getStorageAt` calls. Other [`PrimitivesFetcher`] calls go straight to
+/// the wrapped provider.
+///
+/// Batching only helps concurrent reads, such as the slots read by
+/// `tokio::try_join!` in the fee APIs or by queries over many positions.
+#[derive(Debug, Clone)]
+pub struct BatchedStorageFetcher<N: Network = Ethereum> {
+    provider:       AlloyProviderWrapper<N>,
+    window:         Duration,
+    max_batch_size: usize,
+    pending:        Arc<Mutex<Vec<PendingBatch>>>,
+    next_batch_id:  Arc<AtomicU64>
+}
+
+#[derive(Debug)]
+struct PendingBatch {
+    id:       u64,
+    block_id: BlockId,
+    slots:    Vec<PendingSlot>
+}
+
+#[derive(Debug)]
+struct PendingSlot {
+    address: Address,
+    key:     StorageKey,
+    tx:      oneshot::Sender<eyre::Result<StorageValue>>
+}
+
+impl<N: Network> BatchedStorageFetcher<N> {
+    pub fn new(provider: AlloyProviderWrapper<N>) -> Self {
+        Self {
+            provider,
+            window: DEFAULT_WINDOW,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            pending: Arc::default(),
+            next_batch_id: Arc::default()
+        }
+    }
+
+    /// How long the first slot of a batch waits for others to join it.
+    /// Defaults to 5ms.
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// The most slots sent in one batch. A full batch is sent without waiting
+    /// for the window to close. Defaults to 100.
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    pub fn provider(&self) -> &AlloyProviderWrapper<N> {
+        &self.provider
+    }
+
+    /// Adds the slot to the open batch of its block, opening one if needed.
+    /// Returns the batch to send now if it is full.
+    fn enqueue(&self, block_id: BlockId, slot: PendingSlot) -> Option<PendingBatch> {
+        let mut pending = self.pending.lock().unwrap();
+
+        let idx = match pending.iter().position(|batch| batch.block_id == block_id) {
+            Some(idx) => idx,
+            None => {
+                let id = self.next_batch_id.fetch_add(1, Ordering::Relaxed);
+                pending.push(PendingBatch { id, block_id, slots: Vec::new() });
+
+                let (batches, provider, window) =
+                    (self.pending.clone(), self.provider.clone(), self.window);
+                tokio::spawn(async move {
+                    tokio::time::sleep(window).await;
+                    let batch = {
+                        let mut batches = batches.lock().unwrap();
+                        batches
+                            .iter()
+                            .position(|batch| batch.id == id)
+                            .map(|idx| batches.swap_remove(idx))
+                    };
+                    if let Some(batch) = batch {
+                        send_batch(provider, batch).await;
+                    }
+                });
+
+                pending.len() - 1
+            }
+        };
+
+        pending[idx].slots.push(slot);
+        (pending[idx].slots.len() >= self.max_batch_size).then(|| pending.swap_remove(idx))
+    }
+}
+
+/// Sends the batch and resolves each of its slots, retried and rate limited
+/// like the provider's other reads with the batch counting as one request per
+/// slot. A failed batch fails every slot in it.
+async fn send_batch<N: Network>(provider: AlloyProviderWrapper<N>, batch: PendingBatch) {
+    let client = provider.root().client();
+    let results = provider
+        .retry_batch(batch.slots.len() as u32, || async {
+            let mut request = client.new_batch();
+            let waiters = batch
+                .slots
+                .iter()
+                .map(|slot| {
+                    request.add_call::<_, StorageValue>(
+                        "eth_getStorageAt",
+                        &(slot.address, U256::from_be_bytes(slot.key.0), batch.block_id)
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            request.send().await?;
+
+            let results = join_all(waiters).await;
+            // a slot that was rate limited or timed out retries the whole batch
+            if let Some(Err(e)) = results.iter().find(|res| {
+                res.as_ref()
+                    .is_err_and(|e| is_transient_error(&format!("{e:?}")))
+            }) {
+                return Err(eyre::eyre!("{e:?}"));
+            }
+
+            eyre::Ok(results)
+        })
+        .await;
+
+    match results {
+        Ok(results) => {
+            for (slot, res) in batch.slots.into_iter().zip(results) {
+                let _ = slot.tx.send(res.map_err(|e| eyre::eyre!("{e:?}")));
+            }
+        }
+        Err(e) => {
+            for slot in batch.slots {
+                let _ = slot.tx.send(Err(eyre::eyre!("{e:?}")));
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: Network> StorageSlotFetcher for BatchedStorageFetcher<N> {
+    async fn storage_at(
+        &self,
+        address: Address,
+        key: StorageKey,
+        block_id: BlockId
+    ) -> eyre::Result<StorageValue> {
+        let (tx, rx) = oneshot::channel();
+        if let Some(batch) = self.enqueue(block_id, PendingSlot { address, key, tx }) {
+            tokio::spawn(send_batch(self.provider.clone(), batch));
+        }
+
+        rx.await?
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: Network> PrimitivesFetcher<N> for BatchedStorageFetcher<N> {
+    async fn fetch_logs_primitive(&self, filter: &Filter) -> eyre::Result<Vec<Log>> {
+        self.provider.fetch_logs_primitive(filter).await
+    }
+
+    async fn view_call<IC>(
+        &self,
+        block_id: BlockId,
+        contract: Address,
+        call: IC
+    ) -> eyre::Result<IC::Return>
+    where
+        IC: SolCall + Send + std::fmt::Debug
+    {
+        self.provider.view_call(block_id, contract, call).await
+    }
+
+    async fn view_deploy_call<IC>(
+        &self,
+        block_id: BlockId,
+        tx: <N as Network>::TransactionRequest
+    ) -> eyre::Result<IC::RustType>
+    where
+        IC: SolType + Send
+    {
+        self.provider.view_deploy_call::<IC>(block_id, tx).await
+    }
+
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<AccountInfo> {
+        self.provider
+            .account_info_primitive(address, block_id)
+            .await
+    }
+
+    async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<N>> {
+        self.provider.alloy_root_provider().await
+    }
+
+    async fn block_number_from_block_id(&self, block_id: BlockId) -> eyre::Result<u64> {
+        self.provider.block_number_from_block_id(block_id).await
+    }
+
+    async fn fetch_block_primitive(
+        &self,
+        block_id: BlockId,
+        full: bool
+    ) -> eyre::Result<<N as Network>::BlockResponse> {
+        self.provider.fetch_block_primitive(block_id, full).await
+    }
+
+    async fn tx_success_primitive(&self, tx_hash: TxHash) -> eyre::Result<bool> {
+        self.provider.tx_success_primitive(tx_hash).await
+    }
+
+    async fn tx_by_hash_primitive(
+        &self,
+        tx_hash: TxHash
+    ) -> eyre::Result<Option<<N as Network>::TransactionResponse>> {
+        self.provider.tx_by_hash_primitive(tx_hash).await
+    }
+}
+
+#[cfg(all(test, feature = "l1"))]
+mod tests {
+    use super::*;
+    use crate::l1::{
+        AngstromL1Chain,
+        apis::AngstromL1UserApi,
+        test_utils::{spawn_angstrom_api, valid_test_params::init_valid_position_params}
+    };
+
+    #[tokio::test]
+    async fn test_batched_angstrom_fees() {
+        let pos_info = init_valid_position_params();
+        let provider = spawn_angstrom_api().await.unwrap().eth_provider().clone();
+        let batched = BatchedStorageFetcher::new(provider.clone());
+
+        let direct_fees = provider
+            .angstrom_fees(
+                pos_info.pool_id,
+                pos_info.current_pool_tick,
+                pos_info.position_token_id,
+                pos_info.tick_lower,
+                pos_info.tick_upper,
+                pos_info.block_number.into(),
+                AngstromL1Chain::Mainnet
+            )
+            .await
+            .unwrap();
+        let batched_fees = batched
+            .angstrom_fees(
+                pos_info.pool_id,
+                pos_info.current_pool_tick,
+                pos_info.position_token_id,
+                pos_info.tick_lower,
+                pos_info.tick_upper,
+                pos_info.block_number.into(),
+                AngstromL1Chain::Mainnet
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(direct_fees, batched_fees);
+    }
+}
@@ -0,0 +1,666 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    task::{Context as TaskContext, Poll}
+};
+
+use alloy_eips::BlockId;
+use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, SerializedRequest};
+use alloy_network::{Ethereum, Network, TransactionBuilder};
+use alloy_primitives::{Address, Bytes, StorageKey, StorageValue, TxHash};
+use alloy_provider::{Provider, ProviderBuilder, RootProvider};
+use alloy_rpc_client::RpcClient;
+use alloy_rpc_types::{AccountInfo, Filter, Log};
+use alloy_sol_types::{SolCall, SolType};
+use alloy_transport::{
+    BoxTransport, TransportError, TransportErrorKind, TransportFut, TransportResult
+};
+use eyre::Context;
+use serde::{Serialize, de::DeserializeOwned};
+use tower::Service;
+use uniswap_storage::StorageSlotFetcher;
+
+use crate::types::providers::{AlloyProviderWrapper, primitive_fetcher::PrimitivesFetcher};
+
+/// The responses recorded by a [`RecordingProvider`], keyed by request.
+type Responses = BTreeMap<String, serde_json::Value>;
+
+/// Serializes the writes of fixture files, which recordings made by
+/// concurrent tests share.
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+fn request_key(method: &str, params: impl Serialize) -> String {
+    format!("{method} {}", serde_json::to_string(&params).expect("request params serialize"))
+}
+
+/// The key of a JSON-RPC request sent through a root provider.
+fn rpc_request_key(request: &SerializedRequest) -> String {
+    format!("rpc {} {}", request.method(), request.params().map_or("null", |params| params.get()))
+}
+
+/// The responses of a recording, shared by a [`RecordingProvider`], its clones
+/// and the transport of its root provider.
+#[derive(Debug, Clone)]
+struct Recorder {
+    path:      PathBuf,
+    responses: Arc<Mutex<Responses>>,
+    autosave:  bool
+}
+
+impl Recorder {
+    fn record(&self, key: String, value: serde_json::Value) -> eyre::Result<()> {
+        self.responses.lock().unwrap().insert(key, value);
+        if self.autosave {
+            self.save()?;
+        }
+
+        Ok(())
+    }
+
+    fn save(&self) -> eyre::Result<()> {
+        let _lock = SAVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut responses = match std::fs::read_to_string(&self.path) {
+            Ok(json) => serde_json::from_str::<Responses>(&json)
+                .wrap_err(format!("invalid fixture file: {:?}", self.path))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Responses::new(),
+            Err(e) => return Err(e.into())
+        };
+        responses.extend(self.responses.lock().unwrap().clone());
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&responses)?)?;
+
+        Ok(())
+    }
+}
+
+/// Records the response to every successful request made through the wrapped
+/// provider, so that [`ReplayProvider`] can serve them back. Requests sent
+/// through [`PrimitivesFetcher::alloy_root_provider`] are recorded too,
+/// including the ones that fail, such as reverted calls. Clones share the
+/// recording, which is written by [`Self::save`].
+#[derive(Debug, Clone)]
+pub struct RecordingProvider<N: Network = Ethereum> {
+    provider: AlloyProviderWrapper<N>,
+    recorder: Recorder
+}
+
+impl<N: Network> RecordingProvider<N> {
+    pub fn new(provider: AlloyProviderWrapper<N>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            provider,
+            recorder: Recorder {
+                path:      path.into(),
+                responses: Arc::default(),
+                autosave:  false
+            }
+        }
+    }
+
+    /// Saves the fixture after every recorded response, so that nothing is
+    /// lost when the code being recorded panics.
+    pub fn with_autosave(mut self) -> Self {
+        self.recorder.autosave = true;
+        self
+    }
+
+    pub fn provider(&self) -> &AlloyProviderWrapper<N> {
+        &self.provider
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.recorder.path
+    }
+
+    /// Writes the responses recorded so far to the fixture file, merging them
+    /// with any it already holds.
+    pub fn save(&self) -> eyre::Result<()> {
+        self.recorder.save()
+    }
+
+    fn record<T: Serialize>(&self, key: String, response: eyre::Result<T>) -> eyre::Result<T> {
+        if let Ok(value) = &response {
+            self.recorder.record(key, serde_json::to_value(value)?)?;
+        }
+
+        response
+    }
+}
+
+/// The transport of a [`RecordingProvider`]'s root provider, which records
+/// every response of the node, errors included.
+#[derive(Debug, Clone)]
+struct RecordingTransport {
+    inner:    BoxTransport,
+    recorder: Recorder
+}
+
+impl Service<RequestPacket> for RecordingTransport {
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+    type Response = ResponsePacket;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, packet: RequestPacket) -> Self::Future {
+        let mut inner = self.inner.clone();
+        let recorder = self.recorder.clone();
+
+        Box::pin(async move {
+            let response_packet = inner.call(packet.clone()).await?;
+
+            let requests = match &packet {
+                RequestPacket::Single(request) => std::slice::from_ref(request),
+                RequestPacket::Batch(requests) => requests.as_slice()
+            };
+            let responses = match &response_packet {
+                ResponsePacket::Single(response) => std::slice::from_ref(response),
+                ResponsePacket::Batch(responses) => responses.as_slice()
+            };
+            for response in responses {
+                let Some(request) = requests.iter().find(|request| *request.id() == response.id)
+                else {
+                    continue
+                };
+
+                let mut value = serde_json::to_value(response)
+                    .map_err(|e| TransportErrorKind::custom_str(&e.to_string()))?;
+                if let Some(fields) = value.as_object_mut() {
+                    fields.remove("jsonrpc");
+                    fields.remove("id");
+                }
+                recorder
+                    .record(rpc_request_key(request), value)
+                    .map_err(|e| TransportErrorKind::custom_str(&format!("{e:?}")))?;
+            }
+
+            Ok(response_packet)
+        })
+    }
+}
+
+/// The transport of a [`ReplayProvider`]'s root provider, which serves the
+/// responses recorded by a [`RecordingTransport`].
+#[derive(Debug, Clone)]
+struct ReplayTransport {
+    path:      PathBuf,
+    responses: Arc<Responses>
+}
+
+impl ReplayTransport {
+    fn replay(&self, request: &SerializedRequest) -> TransportResult<Response> {
+        let key = rpc_request_key(request);
+        let mut value = self.responses.get(&key).cloned().ok_or_else(|| {
+            TransportErrorKind::custom_str(&format!(
+                "request not recorded in fixture {:?}: {key}",
+                self.path
+            ))
+        })?;
+
+        if let Some(fields) = value.as_object_mut() {
+            fields.insert("jsonrpc".to_string(), "2.0".into());
+            fields.insert(
+                "id".to_string(),
+                serde_json::to_value(request.id())
+                    .map_err(|e| TransportErrorKind::custom_str(&e.to_string()))?
+            );
+        }
+
+        serde_json::from_str(&value.to_string()).map_err(|e| {
+            TransportErrorKind::custom_str(&format!("invalid recorded response to {key}: {e}"))
+        })
+    }
+}
+
+impl Service<RequestPacket> for ReplayTransport {
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+    type Response = ResponsePacket;
+
+    fn poll_ready(&mut self, _: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, packet: RequestPacket) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            match packet {
+                RequestPacket::Single(request) => {
+                    Ok(ResponsePacket::Single(this.replay(&request)?))
+                }
+                RequestPacket::Batch(requests) => Ok(ResponsePacket::Batch(
+                    requests
+                        .iter()
+                        .map(|request| this.replay(request))
+                        .collect::<TransportResult<_>>()?
+                ))
+            }
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: Network> StorageSlotFetcher for RecordingProvider<N> {
+    async fn storage_at(
+        &self,
+        address: Address,
+        key: StorageKey,
+        block_id: BlockId
+    ) -> eyre::Result<StorageValue> {
+        let response = self.provider.storage_at(address, key, block_id).await;
+        self.record(request_key("eth_getStorageAt", (address, key, block_id)), response)
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: Network> PrimitivesFetcher<N> for RecordingProvider<N> {
+    async fn fetch_logs_primitive(&self, filter: &Filter) -> eyre::Result<Vec<Log>> {
+        let response = self.provider.fetch_logs_primitive(filter).await;
+        self.record(request_key("eth_getLogs", filter), response)
+    }
+
+    async fn view_call<IC>(
+        &self,
+        block_id: BlockId,
+        contract: Address,
+        call: IC
+    ) -> eyre::Result<IC::Return>
+    where
+        IC: SolCall + Send + std::fmt::Debug
+    {
+        let key = request_key("eth_call", (contract, Bytes::from(call.abi_encode()), block_id));
+        let response = self.provider.view_call(block_id, contract, call).await?;
+
+        self.record(key, Ok(Bytes::from(IC::abi_encode_returns(&response))))?;
+        Ok(response)
+    }
+
+    async fn view_deploy_call<IC>(
+        &self,
+        block_id: BlockId,
+        tx: <N as Network>::TransactionRequest
+    ) -> eyre::Result<IC::RustType>
+    where
+        IC: SolType + Send
+    {
+        let key = request_key("eth_call", (tx.input(), block_id));
+        let response = self.provider.view_deploy_call::<IC>(block_id, tx).await?;
+
+        self.record(key, Ok(Bytes::from(IC::abi_encode(&response))))?;
+        Ok(response)
+    }
+
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<AccountInfo> {
+        let response = self
+            .provider
+            .account_info_primitive(address, block_id)
+            .await;
+        self.record(request_key("eth_getAccountInfo", (address, block_id)), response)
+    }
+
+    /// A root provider over the wrapped one's transport, whose requests are
+    /// recorded.
+    async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<N>> {
+        let client = self.provider.root().client();
+        let transport = RecordingTransport {
+            inner:    client.transport().clone(),
+            recorder: self.recorder.clone()
+        };
+
+        Ok(ProviderBuilder::<_, _, N>::default()
+            .connect_client(RpcClient::new(transport, client.is_local())))
+    }
+
+    async fn block_number_from_block_id(&self, block_id: BlockId) -> eyre::Result<u64> {
+        let response = self.provider.block_number_from_block_id(block_id).await;
+        self.record(request_key("eth_blockNumber", block_id), response)
+    }
+
+    async fn fetch_block_primitive(
+        &self,
+        block_id: BlockId,
+        full: bool
+    ) -> eyre::Result<<N as Network>::BlockResponse> {
+        let response = self.provider.fetch_block_primitive(block_id, full).await;
+        self.record(request_key("eth_getBlock", (block_id, full)), response)
+    }
+
+    async fn tx_success_primitive(&self, tx_hash: TxHash) -> eyre::Result<bool> {
+        let response = self.provider.tx_success_primitive(tx_hash).await;
+        self.record(request_key("eth_getTransactionReceipt", tx_hash), response)
+    }
+
+    async fn tx_by_hash_primitive(
+        &self,
+        tx_hash: TxHash
+    ) -> eyre::Result<Option<<N as Network>::TransactionResponse>> {
+        let response = self.provider.tx_by_hash_primitive(tx_hash).await;
+        self.record(request_key("eth_getTransactionByHash", tx_hash), response)
+    }
+}
+
+/// Serves the responses saved by a [`RecordingProvider`] without a node.
+/// Every request that was not recorded fails with an error naming it.
+#[derive(Debug, Clone)]
+pub struct ReplayProvider<N: Network = Ethereum> {
+    path:      PathBuf,
+    responses: Arc<Responses>,
+    root:      RootProvider<N>
+}
+
+impl<N: Network> ReplayProvider<N> {
+    pub fn from_file(path: impl Into<PathBuf>) -> eyre::Result<Self> {
+        let path = path.into();
+        let json = std::fs::read_to_string(&path)
+            .wrap_err(format!("could not read fixture file: {path:?}"))?;
+        let responses: Arc<Responses> = Arc::new(
+            serde_json::from_str(&json).wrap_err(format!("invalid fixture file: {path:?}"))?
+        );
+        let transport = ReplayTransport { path: path.clone(), responses: responses.clone() };
+
+        Ok(Self {
+            path,
+            responses,
+            root: ProviderBuilder::<_, _, N>::default()
+                .connect_client(RpcClient::new(transport, true))
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn replay<T: DeserializeOwned>(&self, key: String) -> eyre::Result<T> {
+        let value = self
+            .responses
+            .get(&key)
+            .ok_or_else(|| eyre::eyre!("request not recorded in fixture {:?}: {key}", self.path))?;
+
+        Ok(serde_json::from_value(value.clone())?)
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: Network> StorageSlotFetcher for ReplayProvider<N> {
+    async fn storage_at(
+        &self,
+        address: Address,
+        key: StorageKey,
+        block_id: BlockId
+    ) -> eyre::Result<StorageValue> {
+        self.replay(request_key("eth_getStorageAt", (address, key, block_id)))
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: Network> PrimitivesFetcher<N> for ReplayProvider<N> {
+    async fn fetch_logs_primitive(&self, filter: &Filter) -> eyre::Result<Vec<Log>> {
+        self.replay(request_key("eth_getLogs", filter))
+    }
+
+    async fn view_call<IC>(
+        &self,
+        block_id: BlockId,
+        contract: Address,
+        call: IC
+    ) -> eyre::Result<IC::Return>
+    where
+        IC: SolCall + Send + std::fmt::Debug
+    {
+        let data: Bytes = self.replay(request_key(
+            "eth_call",
+            (contract, Bytes::from(call.abi_encode()), block_id)
+        ))?;
+
+        Ok(IC::abi_decode_returns(&data).wrap_err(format!(
+            "block_id: {block_id:?}, contract: {contract:?}, call: {call:?}\noutput: {data:?}"
+        ))?)
+    }
+
+    async fn view_deploy_call<IC>(
+        &self,
+        block_id: BlockId,
+        tx: <N as Network>::TransactionRequest
+    ) -> eyre::Result<IC::RustType>
+    where
+        IC: SolType + Send
+    {
+        let data: Bytes = self.replay(request_key("eth_call", (tx.input(), block_id)))?;
+
+        Ok(IC::abi_decode(&data)?)
+    }
+
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<AccountInfo> {
+        self.replay(request_key("eth_getAccountInfo", (address, block_id)))
+    }
+
+    /// A client that serves the requests recorded through a
+    /// [`RecordingProvider`]'s root provider.
+    async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<N>> {
+        Ok(self.root.clone())
+    }
+
+    async fn block_number_from_block_id(&self, block_id: BlockId) -> eyre::Result<u64> {
+        self.replay(request_key("eth_blockNumber", block_id))
+    }
+
+    async fn fetch_block_primitive(
+        &self,
+        block_id: BlockId,
+        full: bool
+    ) -> eyre::Result<<N as Network>::BlockResponse> {
+        self.replay(request_key("eth_getBlock", (block_id, full)))
+    }
+
+    async fn tx_success_primitive(&self, tx_hash: TxHash) -> eyre::Result<bool> {
+        self.replay(request_key("eth_getTransactionReceipt", tx_hash))
+    }
+
+    async fn tx_by_hash_primitive(
+        &self,
+        tx_hash: TxHash
+    ) -> eyre::Result<Option<<N as Network>::TransactionResponse>> {
+        self.replay(request_key("eth_getTransactionByHash", tx_hash))
+    }
+}
+
+/// The provider of the tests: the node at the url when one is set, otherwise
+/// the responses recorded in one of the crate's `fixtures`.
+///
+/// When `RECORD_FIXTURES` is set along with the url, the node's responses are
+/// recorded into the fixture as the tests run, e.g.
+/// `RECORD_FIXTURES=1 ETH_WS_URL=.. cargo test --no-default-features
+/// --features all-chains l1::`. Tests only replay what they requested while
+/// recording, so a fixture is re-recorded whenever a test's requests change.
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub(crate) enum NodeOrReplayProvider<N: Network = Ethereum> {
+    Node(AlloyProviderWrapper<N>),
+    Record(RecordingProvider<N>),
+    Replay(ReplayProvider<N>)
+}
+
+/// Calls the method on whichever provider a [`NodeOrReplayProvider`] holds.
+#[cfg(test)]
+macro_rules! delegate {
+    ($self:ident, $provider:ident => $call:expr) => {
+        match $self {
+            Self::Node($provider) => $call,
+            Self::Record($provider) => $call,
+            Self::Replay($provider) => $call
+        }
+    };
+}
+
+#[cfg(test)]
+impl<N: Network> NodeOrReplayProvider<N> {
+    /// Connects to the node at `url` with `connect`, recording its responses
+    /// into `fixtures/{fixture}` when `RECORD_FIXTURES` is set, or replays the
+    /// fixture without a url.
+    pub(crate) async fn connect_or_replay<F>(
+        url: Option<String>,
+        fixture: &str,
+        connect: impl FnOnce(String) -> F
+    ) -> eyre::Result<Self>
+    where
+        F: Future<Output = eyre::Result<AlloyProviderWrapper<N>>>
+    {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join(fixture);
+
+        match url {
+            Some(url) if std::env::var_os("RECORD_FIXTURES").is_some() => {
+                Ok(Self::Record(RecordingProvider::new(connect(url).await?, path).with_autosave()))
+            }
+            Some(url) => Ok(Self::Node(connect(url).await?)),
+            None => Ok(Self::Replay(ReplayProvider::from_file(path)?))
+        }
+    }
+}
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl<N: Network> StorageSlotFetcher for NodeOrReplayProvider<N> {
+    async fn storage_at(
+        &self,
+        address: Address,
+        key: StorageKey,
+        block_id: BlockId
+    ) -> eyre::Result<StorageValue> {
+        delegate!(self, provider => provider.storage_at(address, key, block_id).await)
+    }
+}
+
+#[cfg(test)]
+#[async_trait::async_trait]
+impl<N: Network> PrimitivesFetcher<N> for NodeOrReplayProvider<N> {
+    async fn fetch_logs_primitive(&self, filter: &Filter) -> eyre::Result<Vec<Log>> {
+        delegate!(self, provider => provider.fetch_logs_primitive(filter).await)
+    }
+
+    async fn view_call<IC>(
+        &self,
+        block_id: BlockId,
+        contract: Address,
+        call: IC
+    ) -> eyre::Result<IC::Return>
+    where
+        IC: SolCall + Send + std::fmt::Debug
+    {
+        delegate!(self, provider => provider.view_call(block_id, contract, call).await)
+    }
+
+    async fn view_deploy_call<IC>(
+        &self,
+        block_id: BlockId,
+        tx: <N as Network>::TransactionRequest
+    ) -> eyre::Result<IC::RustType>
+    where
+        IC: SolType + Send
+    {
+        delegate!(self, provider => provider.view_deploy_call::<IC>(block_id, tx).await)
+    }
+
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<AccountInfo> {
+        delegate!(self, provider => provider.account_info_primitive(address, block_id).await)
+    }
+
+    async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<N>> {
+        delegate!(self, provider => provider.alloy_root_provider().await)
+    }
+
+    async fn block_number_from_block_id(&self, block_id: BlockId) -> eyre::Result<u64> {
+        delegate!(self, provider => provider.block_number_from_block_id(block_id).await)
+    }
+
+    async fn fetch_block_primitive(
+        &self,
+        block_id: BlockId,
+        full: bool
+    ) -> eyre::Result<<N as Network>::BlockResponse> {
+        delegate!(self, provider => provider.fetch_block_primitive(block_id, full).await)
+    }
+
+    async fn tx_success_primitive(&self, tx_hash: TxHash) -> eyre::Result<bool> {
+        delegate!(self, provider => provider.tx_success_primitive(tx_hash).await)
+    }
+
+    async fn tx_by_hash_primitive(
+        &self,
+        tx_hash: TxHash
+    ) -> eyre::Result<Option<<N as Network>::TransactionResponse>> {
+        delegate!(self, provider => provider.tx_by_hash_primitive(tx_hash).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{B256, U64, U256, address};
+    use alloy_transport::mock::Asserter;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let asserter = Asserter::new();
+        let node = AlloyProviderWrapper::new(
+            ProviderBuilder::<_, _, Ethereum>::default().connect_mocked_client(asserter.clone())
+        );
+        let path = std::env::temp_dir().join("angstrom-sdk-test-record-and-replay.json");
+        let _ = std::fs::remove_file(&path);
+
+        let contract = address!("0x0000000000000000000000000000000000001234");
+        let block_id = BlockId::number(100);
+        asserter.push_success(&U256::from(42));
+        asserter.push_success(&U64::from(1));
+        asserter.push_failure_msg("execution reverted");
+
+        let recording = RecordingProvider::new(node, &path);
+        let slot = recording
+            .storage_at(contract, B256::ZERO, block_id)
+            .await
+            .unwrap();
+        let root = recording.alloy_root_provider().await.unwrap();
+        let chain_id = root.get_chain_id().await.unwrap();
+        let reverted = root.get_balance(contract).await.unwrap_err();
+        recording.save().unwrap();
+
+        let replay = ReplayProvider::<Ethereum>::from_file(&path).unwrap();
+        let root = replay.alloy_root_provider().await.unwrap();
+        assert_eq!(
+            replay
+                .storage_at(contract, B256::ZERO, block_id)
+                .await
+                .unwrap(),
+            slot
+        );
+        assert_eq!(root.get_chain_id().await.unwrap(), chain_id);
+        assert_eq!(root.get_balance(contract).await.unwrap_err().to_string(), reverted.to_string());
+
+        let unrecorded = replay
+            .storage_at(contract, B256::ZERO, BlockId::number(101))
+            .await;
+        assert!(unrecorded.is_err());
+        assert!(root.get_block_number().await.is_err());
+    }
+}
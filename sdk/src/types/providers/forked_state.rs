@@ -0,0 +1,375 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock}
+};
+
+use alloy_eips::BlockId;
+use alloy_network::{Ethereum, Network, TransactionBuilder};
+use alloy_primitives::{Address, Bytes, StorageKey, StorageValue, TxHash, TxKind, U256};
+use alloy_provider::{ProviderBuilder, RootProvider};
+use alloy_rpc_types::{AccountInfo as RpcAccountInfo, Filter, Log};
+use alloy_sol_types::{SolCall, SolType};
+use alloy_transport::mock::Asserter;
+use eyre::Context as _;
+use revm::{
+    Context, ExecuteEvm, MainBuilder,
+    context::{BlockEnv, TxEnv},
+    primitives::hardfork::SpecId,
+    state::{AccountInfo, Bytecode}
+};
+use revm_database::{CacheDB, DatabaseRef, EmptyDB, EmptyDBTyped};
+use serde::{Deserialize, Serialize};
+use uniswap_storage::StorageSlotFetcher;
+
+use crate::types::providers::primitive_fetcher::PrimitivesFetcher;
+
+/// The state of a set of accounts at a block, as loaded by
+/// [`ForkedStateProvider::from_json`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StateSnapshot {
+    pub block_number: u64,
+    pub timestamp:    u64,
+    pub chain_id:     u64,
+    pub accounts:     HashMap<Address, AccountSnapshot>
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccountSnapshot {
+    #[serde(default)]
+    pub balance: U256,
+    #[serde(default)]
+    pub nonce:   u64,
+    /// runtime bytecode
+    #[serde(default)]
+    pub code:    Bytes,
+    #[serde(default)]
+    pub storage: HashMap<U256, U256>
+}
+
+/// A [`PrimitivesFetcher`] that serves storage reads and calls from in-memory
+/// state, executed with revm. Every block id resolves to the snapshot's block,
+/// and reads of unknown accounts or slots return zero.
+///
+/// Calls run with revm's default spec unless it is set with
+/// [`ForkedStateProvider::with_spec`]. Logs, blocks and transactions are not
+/// part of the state and return an error.
+#[derive(Debug, Clone)]
+pub struct ForkedStateProvider<N: Network = Ethereum> {
+    db:           Arc<RwLock<CacheDB<EmptyDB>>>,
+    block_number: u64,
+    timestamp:    u64,
+    chain_id:     u64,
+    spec:         SpecId,
+    root:         RootProvider<N>
+}
+
+impl<N: Network> ForkedStateProvider<N> {
+    pub fn new(block_number: u64, timestamp: u64, chain_id: u64) -> Self {
+        Self {
+            db: Arc::new(RwLock::new(CacheDB::new(EmptyDB::default()))),
+            block_number,
+            timestamp,
+            chain_id,
+            spec: SpecId::default(),
+            root: ProviderBuilder::<_, _, N>::default().connect_mocked_client(Asserter::new())
+        }
+    }
+
+    pub fn from_snapshot(snapshot: StateSnapshot) -> Self {
+        let this = Self::new(snapshot.block_number, snapshot.timestamp, snapshot.chain_id);
+        for (address, account) in snapshot.accounts {
+            this.insert_account(address, account.balance, account.nonce, account.code);
+            for (slot, value) in account.storage {
+                this.insert_storage(address, slot, value);
+            }
+        }
+
+        this
+    }
+
+    pub fn from_json(json: &str) -> eyre::Result<Self> {
+        Ok(Self::from_snapshot(serde_json::from_str(json)?))
+    }
+
+    /// The hardfork calls run with, which should be the one active at the
+    /// snapshot's block.
+    pub fn with_spec(mut self, spec: SpecId) -> Self {
+        self.spec = spec;
+        self
+    }
+
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    pub fn insert_account(&self, address: Address, balance: U256, nonce: u64, code: Bytes) {
+        let code = Bytecode::new_raw(code);
+        self.db
+            .write()
+            .unwrap()
+            .insert_account_info(address, AccountInfo::new(balance, nonce, code.hash_slow(), code));
+    }
+
+    pub fn insert_storage(&self, address: Address, slot: U256, value: U256) {
+        self.db
+            .write()
+            .unwrap()
+            .insert_account_storage(address, slot, value)
+            .expect("the empty database cannot fail");
+    }
+
+    /// The current state, including any accounts and slots inserted since it
+    /// was loaded.
+    pub fn to_snapshot(&self) -> StateSnapshot {
+        let db = self.db.read().unwrap();
+        let accounts = db
+            .cache
+            .accounts
+            .iter()
+            .map(|(address, account)| {
+                let code = account
+                    .info
+                    .code
+                    .as_ref()
+                    .map(|code| code.original_bytes())
+                    .unwrap_or_default();
+                let snapshot = AccountSnapshot {
+                    balance: account.info.balance,
+                    nonce: account.info.nonce,
+                    code,
+                    storage: account
+                        .storage
+                        .iter()
+                        .map(|(slot, value)| (*slot, *value))
+                        .collect()
+                };
+                (*address, snapshot)
+            })
+            .collect();
+
+        StateSnapshot {
+            block_number: self.block_number,
+            timestamp: self.timestamp,
+            chain_id: self.chain_id,
+            accounts
+        }
+    }
+
+    fn transact(&self, kind: TxKind, data: Bytes) -> eyre::Result<Bytes> {
+        let db = self.db.read().unwrap();
+        let chain_id = self.chain_id;
+
+        let mut evm = Context::<BlockEnv>::new(EmptyDBTyped::default(), self.spec)
+            .with_ref_db(&*db)
+            .modify_cfg_chained(|cfg| {
+                cfg.chain_id = chain_id;
+                cfg.disable_balance_check = true;
+            })
+            .with_block(BlockEnv {
+                number: U256::from(self.block_number),
+                timestamp: U256::from(self.timestamp),
+                ..Default::default()
+            })
+            .build_mainnet();
+
+        let tx = TxEnv { kind, data, chain_id: Some(chain_id), ..Default::default() };
+        let result = evm.transact(tx).map_err(|e| eyre::eyre!("{e:?}"))?.result;
+
+        if !result.is_success() {
+            return Err(eyre::eyre!("execution failed: {result:?}"));
+        }
+
+        Ok(result.into_output().unwrap_or_default())
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: Network> StorageSlotFetcher for ForkedStateProvider<N> {
+    async fn storage_at(
+        &self,
+        address: Address,
+        key: StorageKey,
+        _: BlockId
+    ) -> eyre::Result<StorageValue> {
+        let db = self.db.read().unwrap();
+        Ok(db
+            .storage_ref(address, U256::from_be_bytes(key.0))
+            .expect("the empty database cannot fail"))
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: Network> PrimitivesFetcher<N> for ForkedStateProvider<N> {
+    async fn fetch_logs_primitive(&self, _: &Filter) -> eyre::Result<Vec<Log>> {
+        Err(eyre::eyre!("logs are not available from a state snapshot"))
+    }
+
+    async fn view_call<IC>(
+        &self,
+        block_id: BlockId,
+        contract: Address,
+        call: IC
+    ) -> eyre::Result<IC::Return>
+    where
+        IC: SolCall + Send + std::fmt::Debug
+    {
+        let data = self.transact(TxKind::Call(contract), call.abi_encode().into())?;
+
+        Ok(IC::abi_decode_returns(&data).wrap_err(format!(
+            "block_id: {block_id:?}, contract: {contract:?}, call: {call:?}\noutput: {data:?}"
+        ))?)
+    }
+
+    async fn view_deploy_call<IC>(
+        &self,
+        _: BlockId,
+        tx: <N as Network>::TransactionRequest
+    ) -> eyre::Result<IC::RustType>
+    where
+        IC: SolType + Send
+    {
+        let data = self.transact(TxKind::Create, tx.input().cloned().unwrap_or_default())?;
+
+        Ok(IC::abi_decode(&data)?)
+    }
+
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        _: BlockId
+    ) -> eyre::Result<RpcAccountInfo> {
+        let info = self
+            .db
+            .read()
+            .unwrap()
+            .basic_ref(address)?
+            .unwrap_or_default();
+
+        Ok(RpcAccountInfo {
+            balance: info.balance,
+            nonce:   info.nonce,
+            code:    info
+                .code
+                .map(|code| code.original_bytes())
+                .unwrap_or_default()
+        })
+    }
+
+    /// A client that fails every request. The APIs only use it to build
+    /// deploy calls.
+    async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<N>> {
+        Ok(self.root.clone())
+    }
+
+    async fn block_number_from_block_id(&self, _: BlockId) -> eyre::Result<u64> {
+        Ok(self.block_number)
+    }
+
+    async fn fetch_block_primitive(
+        &self,
+        block_id: BlockId,
+        _: bool
+    ) -> eyre::Result<<N as Network>::BlockResponse> {
+        Err(eyre::eyre!("block {block_id:?} is not available from a state snapshot"))
+    }
+
+    async fn tx_success_primitive(&self, tx_hash: TxHash) -> eyre::Result<bool> {
+        Err(eyre::eyre!("tx {tx_hash:?} is not available from a state snapshot"))
+    }
+
+    async fn tx_by_hash_primitive(
+        &self,
+        tx_hash: TxHash
+    ) -> eyre::Result<Option<<N as Network>::TransactionResponse>> {
+        Err(eyre::eyre!("tx {tx_hash:?} is not available from a state snapshot"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{B256, address, bytes};
+    use alloy_rpc_types::TransactionRequest;
+    use alloy_sol_types::{sol, sol_data};
+
+    use super::*;
+
+    sol! {
+        function slot0() external view returns (uint256);
+    }
+
+    /// returns storage slot 0
+    const SLOAD_SLOT0: Bytes = bytes!("60005460005260206000f3");
+    /// constructor that returns 7
+    const RETURN_SEVEN: Bytes = bytes!("600760005260206000f3");
+
+    fn provider() -> ForkedStateProvider {
+        let contract = address!("0x0000000000000000000000000000000000001234");
+        let provider = ForkedStateProvider::new(100, 1_700_000_000, 1);
+        provider.insert_account(contract, U256::ZERO, 1, SLOAD_SLOT0);
+        provider.insert_storage(contract, U256::ZERO, U256::from(42));
+        provider
+    }
+
+    #[tokio::test]
+    async fn test_forked_state_calls() {
+        let contract = address!("0x0000000000000000000000000000000000001234");
+        let provider = provider();
+
+        let value = provider
+            .view_call(BlockId::latest(), contract, slot0Call {})
+            .await
+            .unwrap();
+        assert_eq!(value, U256::from(42));
+
+        let slot = provider
+            .storage_at(contract, B256::ZERO, BlockId::latest())
+            .await
+            .unwrap();
+        assert_eq!(slot, U256::from(42));
+
+        let deployed = provider
+            .view_deploy_call::<sol_data::Uint<256>>(
+                BlockId::latest(),
+                TransactionRequest::default().with_deploy_code(RETURN_SEVEN)
+            )
+            .await
+            .unwrap();
+        assert_eq!(deployed, U256::from(7));
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let snapshot = provider().to_snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+
+        let reloaded = ForkedStateProvider::<Ethereum>::from_json(&json).unwrap();
+        assert_eq!(reloaded.to_snapshot(), snapshot);
+    }
+
+    #[cfg(feature = "l1")]
+    #[tokio::test]
+    async fn test_slot0_from_snapshot() {
+        use crate::l1::{
+            AngstromL1Chain, apis::data_api::AngstromL1DataApi,
+            test_utils::valid_test_params::init_valid_position_params
+        };
+
+        let provider = ForkedStateProvider::<Ethereum>::from_json(include_str!(
+            "../../../fixtures/mainnet_pool_slot0.json"
+        ))
+        .unwrap()
+        .with_spec(SpecId::PRAGUE);
+        let pool_manager = AngstromL1Chain::Mainnet
+            .constants()
+            .uniswap_constants()
+            .pool_manager();
+        assert!(provider.to_snapshot().accounts.contains_key(&pool_manager));
+
+        let state = init_valid_position_params();
+        let slot0 = provider
+            .slot0_by_pool_id(state.pool_id, BlockId::latest(), AngstromL1Chain::Mainnet)
+            .await
+            .unwrap();
+        assert_eq!(slot0.tick, state.current_pool_tick);
+    }
+}
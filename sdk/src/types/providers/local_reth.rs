@@ -4,7 +4,7 @@ use alloy_eips::BlockId;
 use alloy_network::{Network, ReceiptResponse, TransactionBuilder};
 use alloy_primitives::{Address, TxHash, TxKind};
 use alloy_provider::RootProvider;
-use alloy_rpc_types::{Block, Filter, Log, TransactionRequest};
+use alloy_rpc_types::{AccountInfo, Block, Filter, Log, TransactionRequest};
 use alloy_sol_types::{SolCall, SolType};
 use eth_network_exts::EthNetworkExt;
 use eyre::Context;
@@ -14,7 +14,7 @@ use lib_reth::{
     reth_libmdbx::{NodeClientSpec, RethNodeClient},
     traits::{EthRevm, EthRevmParams, EthStream, empty_mainnet_revm}
 };
-use revm::context::TxEnv;
+use revm::{DatabaseRef, context::TxEnv};
 
 use crate::types::providers::{AlloyProviderWrapper, primitive_fetcher::PrimitivesFetcher};
 
@@ -123,6 +123,32 @@ where
         Ok(IC::abi_decode(&data.unwrap_or_default())?)
     }
 
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<AccountInfo> {
+        let chain_id = <N as EthNetworkExt>::CHAIN_ID;
+
+        let evm_db = self
+            .provider
+            .make_cache_db(&EthRevmParams { block_id, chain_id })?;
+
+        let info = evm_db
+            .basic_ref(address)
+            .map_err(|e| eyre::eyre!("{e:?}"))?
+            .unwrap_or_default();
+        let code = match info.code {
+            Some(code) => code.original_bytes(),
+            None => evm_db
+                .code_by_hash_ref(info.code_hash)
+                .map_err(|e| eyre::eyre!("{e:?}"))?
+                .original_bytes()
+        };
+
+        Ok(AccountInfo { balance: info.balance, nonce: info.nonce, code })
+    }
+
     async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<N::AlloyNetwork>> {
         Ok(self.provider().root_provider().await?)
     }
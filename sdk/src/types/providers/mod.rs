@@ -6,6 +6,28 @@ pub use local_reth::*;
 mod alloy_provider;
 pub use alloy_provider::AlloyProviderWrapper;
 
+mod batched_storage;
+pub use batched_storage::BatchedStorageFetcher;
+
+mod fixture;
+#[cfg(test)]
+pub(crate) use fixture::NodeOrReplayProvider;
+pub use fixture::{RecordingProvider, ReplayProvider};
+
+#[cfg(feature = "forked-state")]
+mod forked_state;
+#[cfg(feature = "forked-state")]
+pub use forked_state::{AccountSnapshot, ForkedStateProvider, StateSnapshot};
+
+mod multi_provider;
+pub use multi_provider::{MultiProviderWrapper, ProviderHealth};
+
+mod retry;
+pub use retry::{RateLimiter, RetryPolicy};
+
 mod storage;
 
+mod verified_storage;
+pub use verified_storage::{StateRootSource, VerifiedStorageFetcher};
+
 pub mod primitive_fetcher;
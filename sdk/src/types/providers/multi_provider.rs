@@ -0,0 +1,523 @@
+use std::{
+    future::Future,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering}
+    },
+    task::{Context as TaskContext, Poll},
+    time::{Duration, Instant}
+};
+
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_network::{Ethereum, Network, TransactionBuilder};
+use alloy_primitives::{Address, StorageKey, StorageValue, TxHash};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_client::RpcClient;
+use alloy_rpc_types::{AccountInfo, Filter, Log};
+use alloy_sol_types::{SolCall, SolType};
+use alloy_transport::{TransportError, TransportErrorKind, TransportFut};
+use eyre::Context;
+use tower::Service;
+use uniswap_storage::StorageSlotFetcher;
+
+use crate::types::providers::{
+    AlloyProviderWrapper, primitive_fetcher::PrimitivesFetcher, retry::is_transient_error
+};
+
+const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Routes reads over several [`AlloyProviderWrapper`]s. Each request is sent
+/// to a backend picked round-robin or by weight, and retried on the next
+/// backends if it fails with a transient error such as a rate limit or a
+/// timeout. Backends that fail repeatedly are marked unhealthy and only tried
+/// after the healthy ones until their cooldown passes.
+///
+/// Other errors, such as reverts or decoding failures, would fail the same
+/// way on every backend and are returned as is.
+#[derive(Debug, Clone)]
+pub struct MultiProviderWrapper<N: Network = Ethereum> {
+    backends:          Arc<[Backend<N>]>,
+    next:              Arc<AtomicUsize>,
+    max_attempts:      usize,
+    pin_blocks:        bool,
+    failure_threshold: u32,
+    cooldown:          Duration
+}
+
+#[derive(Debug)]
+struct Backend<N: Network> {
+    provider: AlloyProviderWrapper<N>,
+    weight:   u32,
+    health:   BackendHealth
+}
+
+#[derive(Debug, Default)]
+struct BackendHealth {
+    requests:             AtomicU64,
+    failures:             AtomicU64,
+    consecutive_failures: AtomicU32,
+    unhealthy_until:      Mutex<Option<Instant>>
+}
+
+impl BackendHealth {
+    fn is_healthy(&self) -> bool {
+        self.unhealthy_until
+            .lock()
+            .unwrap()
+            .is_none_or(|until| until <= Instant::now())
+    }
+
+    fn record_success(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        *self.unhealthy_until.lock().unwrap() = None;
+    }
+
+    fn record_failure(&self, failure_threshold: u32, cooldown: Duration) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.failures.fetch_add(1, Ordering::Relaxed);
+        let consecutive_failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if consecutive_failures >= failure_threshold {
+            *self.unhealthy_until.lock().unwrap() = Some(Instant::now() + cooldown);
+        }
+    }
+}
+
+/// A snapshot of a backend's request counts and health.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderHealth {
+    pub requests:             u64,
+    pub failures:             u64,
+    pub consecutive_failures: u32,
+    pub healthy:              bool
+}
+
+impl<N: Network> MultiProviderWrapper<N> {
+    /// Routes requests round-robin over `providers`.
+    pub fn new(providers: impl IntoIterator<Item = AlloyProviderWrapper<N>>) -> Self {
+        Self::weighted(providers.into_iter().map(|provider| (provider, 1)))
+    }
+
+    /// Routes requests over `providers` in proportion to their weights. A
+    /// backend with a weight of 0 is only used as a fallback.
+    pub fn weighted(providers: impl IntoIterator<Item = (AlloyProviderWrapper<N>, u32)>) -> Self {
+        let backends = providers
+            .into_iter()
+            .map(|(provider, weight)| Backend {
+                provider,
+                weight,
+                health: BackendHealth::default()
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            max_attempts:      backends.len(),
+            backends:          backends.into(),
+            next:              Arc::new(AtomicUsize::new(0)),
+            pin_blocks:        false,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            cooldown:          DEFAULT_COOLDOWN
+        }
+    }
+
+    /// The number of backends a request is sent to before giving up.
+    /// Defaults to every backend.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Resolves block tags such as `latest` to a block number before sending
+    /// a request, so that retries and later requests do not read from
+    /// backends at different heads.
+    pub fn with_pinned_blocks(mut self, pin_blocks: bool) -> Self {
+        self.pin_blocks = pin_blocks;
+        self
+    }
+
+    /// Marks a backend unhealthy for `cooldown` after `failure_threshold`
+    /// consecutive failures. Defaults to 3 failures and 30 seconds.
+    pub fn with_unhealthy_threshold(mut self, failure_threshold: u32, cooldown: Duration) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self.cooldown = cooldown;
+        self
+    }
+
+    pub fn providers(&self) -> impl Iterator<Item = &AlloyProviderWrapper<N>> {
+        self.backends.iter().map(|backend| &backend.provider)
+    }
+
+    /// A client that sends every request through the routing and failover,
+    /// to back any alloy provider, such as one built with
+    /// [`ProviderBuilder`](alloy_provider::ProviderBuilder).
+    pub fn into_client(self) -> RpcClient {
+        RpcClient::new(self, false)
+    }
+
+    /// The health of each backend, in the order they were added.
+    pub fn health(&self) -> Vec<ProviderHealth> {
+        self.backends
+            .iter()
+            .map(|backend| ProviderHealth {
+                requests:             backend.health.requests.load(Ordering::Relaxed),
+                failures:             backend.health.failures.load(Ordering::Relaxed),
+                consecutive_failures: backend.health.consecutive_failures.load(Ordering::Relaxed),
+                healthy:              backend.health.is_healthy()
+            })
+            .collect()
+    }
+
+    fn route(&self) -> Vec<usize> {
+        let weights = self
+            .backends
+            .iter()
+            .map(|backend| backend.weight)
+            .collect::<Vec<_>>();
+        let healthy = self
+            .backends
+            .iter()
+            .map(|backend| backend.health.is_healthy())
+            .collect::<Vec<_>>();
+
+        route_order(&weights, &healthy, self.next.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Sends `request` to the routed backends until one succeeds or fails
+    /// with an error that is not transient.
+    async fn fallback<T, F, Fut>(&self, request: F) -> eyre::Result<T>
+    where
+        F: Fn(AlloyProviderWrapper<N>) -> Fut + Send + Sync,
+        Fut: Future<Output = eyre::Result<T>> + Send
+    {
+        let mut last_err = None;
+        for idx in self.route().into_iter().take(self.max_attempts) {
+            let backend = &self.backends[idx];
+            match request(backend.provider.clone()).await {
+                Ok(val) => {
+                    backend.health.record_success();
+                    return Ok(val);
+                }
+                // the backend answered, the request itself is at fault
+                Err(e) if !is_transient_error(&format!("{e:?}")) => {
+                    backend.health.record_success();
+                    return Err(e);
+                }
+                Err(e) => {
+                    backend
+                        .health
+                        .record_failure(self.failure_threshold, self.cooldown);
+                    last_err = Some(e);
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| eyre::eyre!("no providers configured")))
+    }
+
+    /// Resolves `block_id` to a block number if blocks are pinned. Hashes
+    /// and the pending block are left as is.
+    async fn pin(&self, block_id: BlockId) -> eyre::Result<BlockId> {
+        if !self.pin_blocks
+            || block_id.as_u64().is_some()
+            || matches!(block_id, BlockId::Hash(_) | BlockId::Number(BlockNumberOrTag::Pending))
+        {
+            return Ok(block_id);
+        }
+
+        Ok(self.block_number_from_block_id(block_id).await?.into())
+    }
+
+    async fn pin_filter(&self, filter: &Filter) -> eyre::Result<Filter> {
+        let mut filter = filter.clone();
+        if let Some(&from_block) = filter.block_option.get_from_block()
+            && let Some(block_number) = self.pin(from_block.into()).await?.as_u64()
+        {
+            filter = filter.from_block(block_number);
+        }
+        if let Some(&to_block) = filter.block_option.get_to_block()
+            && let Some(block_number) = self.pin(to_block.into()).await?.as_u64()
+        {
+            filter = filter.to_block(block_number);
+        }
+
+        Ok(filter)
+    }
+}
+
+/// The order to try backends in: the backend picked by `ticket` over the
+/// cumulative weights first, then the rest in order, with unhealthy backends
+/// moved to the end.
+fn route_order(weights: &[u32], healthy: &[bool], ticket: usize) -> Vec<usize> {
+    let backends = weights.len();
+    if backends == 0 {
+        return Vec::new();
+    }
+
+    let total_weight = weights.iter().map(|weight| *weight as u64).sum::<u64>();
+    let start = if total_weight == 0 {
+        ticket % backends
+    } else {
+        let mut point = ticket as u64 % total_weight;
+        weights
+            .iter()
+            .position(|weight| {
+                let picked = point < *weight as u64;
+                point = point.saturating_sub(*weight as u64);
+                picked
+            })
+            .unwrap_or_default()
+    };
+
+    let mut order = (0..backends)
+        .map(|i| (start + i) % backends)
+        .collect::<Vec<_>>();
+    order.sort_by_key(|idx| !healthy[*idx]);
+    order
+}
+
+/// Routes raw JSON-RPC packets, for [`MultiProviderWrapper::into_client`]. A
+/// packet is also sent to the next backend if one of its responses is a
+/// transient error, such as a rate limit.
+impl<N: Network> Service<RequestPacket> for MultiProviderWrapper<N> {
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+    type Response = ResponsePacket;
+
+    fn poll_ready(&mut self, _: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, packet: RequestPacket) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            this.fallback(|provider| {
+                let mut transport = provider.root().client().transport().clone();
+                let packet = packet.clone();
+                async move {
+                    let response = transport.call(packet).await?;
+                    if let Some(error) = response
+                        .iter_errors()
+                        .find(|error| is_transient_error(&format!("{error:?}")))
+                    {
+                        eyre::bail!("{error:?}");
+                    }
+
+                    Ok(response)
+                }
+            })
+            .await
+            .map_err(|e| TransportErrorKind::custom_str(&format!("{e:?}")))
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: Network> StorageSlotFetcher for MultiProviderWrapper<N> {
+    async fn storage_at(
+        &self,
+        address: Address,
+        key: StorageKey,
+        block_id: BlockId
+    ) -> eyre::Result<StorageValue> {
+        let block_id = self.pin(block_id).await?;
+        self.fallback(|provider| async move { provider.storage_at(address, key, block_id).await })
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<N: Network> PrimitivesFetcher<N> for MultiProviderWrapper<N> {
+    async fn fetch_logs_primitive(&self, filter: &Filter) -> eyre::Result<Vec<Log>> {
+        let filter = &self.pin_filter(filter).await?;
+        self.fallback(|provider| async move { provider.fetch_logs_primitive(filter).await })
+            .await
+    }
+
+    async fn view_call<IC>(
+        &self,
+        block_id: BlockId,
+        contract: Address,
+        call: IC
+    ) -> eyre::Result<IC::Return>
+    where
+        IC: SolCall + Send + std::fmt::Debug
+    {
+        let block_id = self.pin(block_id).await?;
+        let mut tx = N::TransactionRequest::default();
+        tx.set_to(contract);
+        tx.set_input(call.abi_encode());
+
+        let data = self
+            .fallback(|provider| {
+                let tx = tx.clone();
+                async move { provider.call_at(tx, block_id).await }
+            })
+            .await?;
+
+        Ok(IC::abi_decode_returns(&data).wrap_err(format!(
+            "block_id: {block_id:?}, contract: {contract:?}, call: {call:?}\noutput: {data:?}"
+        ))?)
+    }
+
+    async fn view_deploy_call<IC>(
+        &self,
+        block_id: BlockId,
+        tx: <N as Network>::TransactionRequest
+    ) -> eyre::Result<IC::RustType>
+    where
+        IC: SolType + Send
+    {
+        let block_id = self.pin(block_id).await?;
+        self.fallback(|provider| {
+            let tx = tx.clone();
+            async move { provider.view_deploy_call::<IC>(block_id, tx).await }
+        })
+        .await
+    }
+
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<AccountInfo> {
+        let block_id = self.pin(block_id).await?;
+        self.fallback(
+            |provider| async move { provider.account_info_primitive(address, block_id).await }
+        )
+        .await
+    }
+
+    /// The root provider of the next routed backend. Requests sent through it
+    /// are not retried.
+    async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<N>> {
+        let idx = self
+            .route()
+            .first()
+            .copied()
+            .ok_or_else(|| eyre::eyre!("no providers configured"))?;
+        Ok(self.backends[idx].provider.root().clone())
+    }
+
+    async fn block_number_from_block_id(&self, block_id: BlockId) -> eyre::Result<u64> {
+        if let Some(block_number) = block_id.as_u64() {
+            return Ok(block_number);
+        }
+
+        self.fallback(|provider| async move { provider.block_number_from_block_id(block_id).await })
+            .await
+    }
+
+    async fn fetch_block_primitive(
+        &self,
+        block_id: BlockId,
+        full: bool
+    ) -> eyre::Result<<N as Network>::BlockResponse> {
+        let block_id = self.pin(block_id).await?;
+        self.fallback(
+            |provider| async move { provider.fetch_block_primitive(block_id, full).await }
+        )
+        .await
+    }
+
+    async fn tx_success_primitive(&self, tx_hash: TxHash) -> eyre::Result<bool> {
+        self.fallback(|provider| async move { provider.tx_success_primitive(tx_hash).await })
+            .await
+    }
+
+    async fn tx_by_hash_primitive(
+        &self,
+        tx_hash: TxHash
+    ) -> eyre::Result<Option<<N as Network>::TransactionResponse>> {
+        self.fallback(|provider| async move { provider.tx_by_hash_primitive(tx_hash).await })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{B256, U256};
+    use alloy_provider::ProviderBuilder;
+    use alloy_transport::mock::Asserter;
+
+    use super::*;
+
+    fn mocked(asserter: &Asserter) -> AlloyProviderWrapper {
+        AlloyProviderWrapper::new(
+            ProviderBuilder::<_, _, Ethereum>::default().connect_mocked_client(asserter.clone())
+        )
+    }
+
+    #[test]
+    fn test_route_order_round_robin() {
+        let weights = [1, 1, 1];
+        let healthy = [true, true, true];
+
+        assert_eq!(route_order(&weights, &healthy, 0), vec![0, 1, 2]);
+        assert_eq!(route_order(&weights, &healthy, 1), vec![1, 2, 0]);
+        assert_eq!(route_order(&weights, &healthy, 5), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn test_route_order_weighted() {
+        let weights = [3, 0, 1];
+        let healthy = [true, true, true];
+
+        let starts = (0..8)
+            .map(|ticket| route_order(&weights, &healthy, ticket)[0])
+            .collect::<Vec<_>>();
+
+        assert_eq!(starts, vec![0, 0, 0, 2, 0, 0, 0, 2]);
+    }
+
+    #[test]
+    fn test_route_order_skips_unhealthy() {
+        let weights = [1, 1, 1];
+        let healthy = [false, true, true];
+
+        assert_eq!(route_order(&weights, &healthy, 0), vec![1, 2, 0]);
+        assert_eq!(route_order(&weights, &healthy, 2), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn test_backend_health() {
+        let health = BackendHealth::default();
+
+        health.record_failure(2, Duration::from_secs(60));
+        assert!(health.is_healthy());
+        health.record_failure(2, Duration::from_secs(60));
+        assert!(!health.is_healthy());
+        health.record_success();
+        assert!(health.is_healthy());
+        assert_eq!(health.failures.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_on_transient_errors_only() {
+        let (first, second) = (Asserter::new(), Asserter::new());
+        let providers = MultiProviderWrapper::new([mocked(&first), mocked(&second)]);
+
+        first.push_failure_msg("429 Too Many Requests");
+        second.push_success(&U256::from(7));
+        let slot = providers
+            .storage_at(Address::ZERO, B256::ZERO, BlockId::latest())
+            .await
+            .unwrap();
+        assert_eq!(slot, U256::from(7));
+
+        // round robin starts the next request on the second backend
+        second.push_failure_msg("execution reverted");
+        first.push_success(&U256::from(7));
+        let reverted = providers
+            .storage_at(Address::ZERO, B256::ZERO, BlockId::latest())
+            .await;
+        assert!(reverted.is_err());
+        assert_eq!(first.read_q().len(), 1);
+
+        let health = providers.health();
+        assert_eq!(health[0].failures, 1);
+        assert_eq!(health[1].failures, 0);
+    }
+}
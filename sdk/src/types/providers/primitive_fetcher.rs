@@ -3,8 +3,8 @@ use std::fmt::Debug;
 use alloy_eips::BlockId;
 use alloy_network::Network;
 use alloy_primitives::{Address, TxHash};
-use alloy_provider::RootProvider;
-use alloy_rpc_types::{Filter, Log};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::{AccountInfo, Filter, Log};
 use alloy_sol_types::{SolCall, SolType};
 
 #[async_trait::async_trait]
@@ -29,6 +29,32 @@ pub trait PrimitivesFetcher<N: Network>: Send + Sync {
     where
         IC: SolType + Send;
 
+    /// The balance, nonce and code of `address`. Defaults to reading them
+    /// through [`PrimitivesFetcher::alloy_root_provider`].
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<AccountInfo> {
+        let provider = self.alloy_root_provider().await?;
+        let (balance, nonce, code) = tokio::try_join!(
+            provider
+                .get_balance(address)
+                .block_id(block_id)
+                .into_future(),
+            provider
+                .get_transaction_count(address)
+                .block_id(block_id)
+                .into_future(),
+            provider
+                .get_code_at(address)
+                .block_id(block_id)
+                .into_future()
+        )?;
+
+        Ok(AccountInfo { balance, nonce, code })
+    }
+
     async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<N>>;
 
     async fn block_number_from_block_id(&self, block_id: BlockId) -> eyre::Result<u64>;
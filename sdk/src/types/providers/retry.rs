@@ -0,0 +1,294 @@
+use std::{
+    fmt::Debug,
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant}
+};
+
+/// Error messages of transient failures: rate limits, timeouts, dropped
+/// connections and nodes that have not caught up yet.
+const TRANSIENT_ERRORS: &[&str] = &[
+    "status: 429",
+    "code: 429",
+    "too many requests",
+    "rate limit",
+    "ratelimit",
+    "exceeded the rate",
+    "capacity exceeded",
+    "timed out",
+    "timeout",
+    "connection reset",
+    "connection refused",
+    "connection closed",
+    "status: 502",
+    "status: 503",
+    "status: 504",
+    "temporarily unavailable",
+    "header not found"
+];
+
+/// `eth_getLogs` errors of the major RPC vendors for a query that covers too
+/// many blocks or returns too many logs.
+const RANGE_LIMIT_ERRORS: &[&str] = &[
+    // reth
+    "query exceeds max results",
+    // alchemy
+    "log response size exceeded",
+    "response size exceeded",
+    // infura
+    "query returned more than",
+    // quicknode
+    "eth_getlogs is limited to",
+    // ankr, chainstack
+    "exceed maximum block range",
+    "block range too large",
+    "block range is too large",
+    "block range limit",
+    "maximum block range",
+    "range is too large",
+    // erigon, geth
+    "query timeout exceeded",
+    "too many results",
+    "logs matched by query exceeds limit"
+];
+
+/// Whether `message` is a range limit error that is worth retrying with a
+/// smaller block range.
+pub(crate) fn is_range_limit_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    RANGE_LIMIT_ERRORS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Whether `message` is a transient error that is worth retrying as is.
+pub(crate) fn is_transient_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    !is_range_limit_error(&message)
+        && TRANSIENT_ERRORS
+            .iter()
+            .any(|pattern| message.contains(pattern))
+}
+
+/// How transient errors are retried, with exponential backoff between the
+/// attempts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries:     u32,
+    pub initial_backoff: Duration,
+    pub max_backoff:     Duration,
+    pub multiplier:      f64
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries:     5,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff:     Duration::from_secs(10),
+            multiplier:      2.0
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retries.
+    pub fn none() -> Self {
+        Self { max_retries: 0, ..Default::default() }
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// The wait before retry number `attempt`, starting at 0.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .mul_f64(self.multiplier.powi(attempt as i32))
+            .min(self.max_backoff)
+    }
+
+    /// Runs `request`, retrying it while it fails with a transient error.
+    /// Every attempt first waits on `rate_limiter`.
+    pub(crate) async fn retry<T, E, F, Fut>(
+        &self,
+        rate_limiter: Option<&RateLimiter>,
+        request: F
+    ) -> Result<T, E>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Debug
+    {
+        self.retry_batch(rate_limiter, 1, request).await
+    }
+
+    /// [`RetryPolicy::retry`] for a JSON-RPC batch, which counts as
+    /// `requests` requests against `rate_limiter`.
+    pub(crate) async fn retry_batch<T, E, F, Fut>(
+        &self,
+        rate_limiter: Option<&RateLimiter>,
+        requests: u32,
+        request: F
+    ) -> Result<T, E>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Debug
+    {
+        let mut attempt = 0;
+        loop {
+            if let Some(rate_limiter) = rate_limiter {
+                rate_limiter.acquire_many(requests).await;
+            }
+
+            match request().await {
+                Err(e) if attempt < self.max_retries && is_transient_error(&format!("{e:?}")) => {
+                    tokio::time::sleep(self.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                res => return res
+            }
+        }
+    }
+}
+
+/// A token bucket that allows `burst` requests at once and refills at
+/// `requests_per_second`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_second: f64,
+    burst:               f64,
+    bucket:              Mutex<TokenBucket>
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    /// negative when requests are waiting on tokens
+    tokens:  f64,
+    updated: Instant
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        let burst = burst.max(1) as f64;
+        Self {
+            requests_per_second: requests_per_second.max(f64::MIN_POSITIVE),
+            burst,
+            bucket: Mutex::new(TokenBucket { tokens: burst, updated: Instant::now() })
+        }
+    }
+
+    /// Waits until a request may be sent.
+    pub async fn acquire(&self) {
+        self.acquire_many(1).await
+    }
+
+    /// Waits until `requests` requests may be sent at once.
+    pub async fn acquire_many(&self, requests: u32) {
+        let wait = self.reserve(Instant::now(), requests);
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Takes `requests` tokens, returning how long to wait until they are
+    /// available.
+    fn reserve(&self, now: Instant, requests: u32) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap();
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.requests_per_second).min(self.burst);
+        bucket.updated = now;
+        bucket.tokens -= requests as f64;
+
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.requests_per_second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_classification() {
+        assert!(is_range_limit_error("Log response size exceeded. Use a 2K block range"));
+        assert!(is_range_limit_error("query returned more than 10000 results"));
+        assert!(is_range_limit_error("eth_getLogs is limited to a 10000 range"));
+        assert!(is_range_limit_error("exceed maximum block range: 50000"));
+        assert!(!is_range_limit_error("execution reverted"));
+
+        assert!(is_transient_error("HttpError { status: 429, body: \"\" }"));
+        assert!(is_transient_error("Too Many Requests"));
+        assert!(is_transient_error("operation timed out"));
+        assert!(!is_transient_error("query timeout exceeded"));
+        assert!(!is_transient_error("execution reverted"));
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(500))
+            .with_multiplier(2.0);
+
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(3), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_rate_limiter_reserve() {
+        let limiter = RateLimiter::new(10.0, 2);
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve(now, 1), Duration::ZERO);
+        assert_eq!(limiter.reserve(now, 1), Duration::ZERO);
+        assert_eq!(limiter.reserve(now, 1), Duration::from_millis(100));
+        assert_eq!(limiter.reserve(now, 1), Duration::from_millis(200));
+        assert_eq!(limiter.reserve(now + Duration::from_secs(1), 1), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_rate_limiter_reserve_many() {
+        let limiter = RateLimiter::new(10.0, 5);
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve(now, 5), Duration::ZERO);
+        assert_eq!(limiter.reserve(now, 3), Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+        let policy = RetryPolicy::default()
+            .with_max_retries(2)
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(1));
+
+        let res = policy
+            .retry(None, || async {
+                attempts.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                Err::<(), _>("too many requests")
+            })
+            .await;
+
+        assert!(res.is_err());
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::Relaxed), 3);
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::BTreeMap;
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockId;
+use alloy_network::{BlockResponse, Ethereum, Network};
+use alloy_primitives::{Address, B256, StorageKey, StorageValue, TxHash, keccak256};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::{AccountInfo, EIP1186AccountProofResponse, EIP1186StorageProof, Filter, Log};
+use alloy_sol_types::{SolCall, SolType};
+use alloy_trie::{EMPTY_ROOT_HASH, KECCAK_EMPTY, Nibbles, TrieAccount, proof::verify_proof};
+use uniswap_storage::StorageSlotFetcher;
+
+use crate::types::providers::{AlloyProviderWrapper, primitive_fetcher::PrimitivesFetcher};
+
+/// A source of block state roots that is trusted, such as a light client, a
+/// node run by the caller, or roots pinned ahead of time.
+#[async_trait::async_trait]
+pub trait StateRootSource: Send + Sync {
+    /// The number and state root of the block.
+    async fn state_root(&self, block_id: BlockId) -> eyre::Result<(u64, B256)>;
+}
+
+#[async_trait::async_trait]
+impl<N: Network> StateRootSource for AlloyProviderWrapper<N> {
+    async fn state_root(&self, block_id: BlockId) -> eyre::Result<(u64, B256)> {
+        let block = self.fetch_block_primitive(block_id, false).await?;
+        let header = block.header();
+
+        Ok((header.number(), header.state_root()))
+    }
+}
+
+/// State roots pinned by block number.
+#[async_trait::async_trait]
+impl StateRootSource for BTreeMap<u64, B256> {
+    async fn state_root(&self, block_id: BlockId) -> eyre::Result<(u64, B256)> {
+        let number = block_id
+            .as_u64()
+            .ok_or_else(|| eyre::eyre!("pinned state roots need a block number: {block_id:?}"))?;
+        let root = self
+            .get(&number)
+            .ok_or_else(|| eyre::eyre!("no pinned state root for block {number}"))?;
+
+        Ok((number, *root))
+    }
+}
+
+/// A [`StorageSlotFetcher`] that does not trust the RPC it reads from. Every
+/// slot is read with `eth_getProof`, and the account and storage proofs are
+/// checked against the state root given by `S`. A read whose proof does not
+/// match fails.
+///
+/// Accounts are verified the same way, along with their code. Other
+/// [`PrimitivesFetcher`] calls go straight to the wrapped provider and are not
+/// verified.
+#[derive(Debug, Clone)]
+pub struct VerifiedStorageFetcher<S, N: Network = Ethereum> {
+    provider:     AlloyProviderWrapper<N>,
+    trusted_root: S
+}
+
+impl<S: StateRootSource, N: Network> VerifiedStorageFetcher<S, N> {
+    pub fn new(provider: AlloyProviderWrapper<N>, trusted_root: S) -> Self {
+        Self { provider, trusted_root }
+    }
+
+    pub fn provider(&self) -> &AlloyProviderWrapper<N> {
+        &self.provider
+    }
+
+    /// The account's balance, nonce, code hash and storage root, verified
+    /// against the trusted state root.
+    pub async fn account_at(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<TrieAccount> {
+        let (_, proof) = self.verified_proof(address, Vec::new(), block_id).await?;
+        Ok(trie_account(&proof))
+    }
+
+    /// Fetches the proof at the trusted block and verifies it, returning the
+    /// number of the trusted block along with it.
+    async fn verified_proof(
+        &self,
+        address: Address,
+        keys: Vec<StorageKey>,
+        block_id: BlockId
+    ) -> eyre::Result<(u64, EIP1186AccountProofResponse)> {
+        let (number, state_root) = self.trusted_root.state_root(block_id).await?;
+        let proof = self
+            .provider
+            .get_proof(address, keys.clone())
+            .block_id(number.into())
+            .await?;
+
+        if proof.address != address {
+            return Err(eyre::eyre!(
+                "proof for {:?} returned for {address:?} at block {number}",
+                proof.address
+            ));
+        }
+        verify_account_proof(state_root, &proof)
+            .map_err(|e| eyre::eyre!("{e} (address: {address:?}, block: {number})"))?;
+
+        if proof.storage_proof.len() != keys.len()
+            || proof
+                .storage_proof
+                .iter()
+                .zip(&keys)
+                .any(|(slot, key)| slot.key.as_b256() != *key)
+        {
+            return Err(eyre::eyre!(
+                "storage proofs for {address:?} at block {number} do not match the requested slots"
+            ));
+        }
+        for slot in &proof.storage_proof {
+            verify_slot_proof(proof.storage_hash, slot).map_err(|e| {
+                eyre::eyre!("{e} (address: {address:?}, slot: {:?}, block: {number})", slot.key)
+            })?;
+        }
+
+        Ok((number, proof))
+    }
+}
+
+fn trie_account(proof: &EIP1186AccountProofResponse) -> TrieAccount {
+    TrieAccount {
+        nonce:        proof.nonce,
+        balance:      proof.balance,
+        storage_root: proof.storage_hash,
+        code_hash:    proof.code_hash
+    }
+}
+
+/// Checks the account proof against the state root. An account that does not
+/// exist is proven by its absence from the trie.
+fn verify_account_proof(state_root: B256, proof: &EIP1186AccountProofResponse) -> eyre::Result<()> {
+    let account = trie_account(proof);
+    let is_empty = account.nonce == 0
+        && account.balance.is_zero()
+        && account.code_hash == KECCAK_EMPTY
+        && account.storage_root == EMPTY_ROOT_HASH;
+    let expected = (!is_empty).then(|| alloy_rlp::encode(account));
+
+    verify_proof(
+        state_root,
+        Nibbles::unpack(keccak256(proof.address)),
+        expected,
+        &proof.account_proof
+    )
+    .map_err(|e| eyre::eyre!("invalid account proof: {e:?}"))
+}
+
+/// Checks the slot proof against the account's storage root. A zero slot is
+/// proven by its absence from the trie.
+fn verify_slot_proof(storage_root: B256, slot: &EIP1186StorageProof) -> eyre::Result<()> {
+    let expected = (!slot.value.is_zero()).then(|| alloy_rlp::encode(slot.value));
+
+    verify_proof(
+        storage_root,
+        Nibbles::unpack(keccak256(slot.key.as_b256())),
+        expected,
+        &slot.proof
+    )
+    .map_err(|e| eyre::eyre!("invalid storage proof: {e:?}"))
+}
+
+#[async_trait::async_trait]
+impl<S: StateRootSource, N: Network> StorageSlotFetcher for VerifiedStorageFetcher<S, N> {
+    async fn storage_at(
+        &self,
+        address: Address,
+        key: StorageKey,
+        block_id: BlockId
+    ) -> eyre::Result<StorageValue> {
+        let (_, proof) = self.verified_proof(address, vec![key], block_id).await?;
+        Ok(proof.storage_proof[0].value)
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: StateRootSource, N: Network> PrimitivesFetcher<N> for VerifiedStorageFetcher<S, N> {
+    async fn fetch_logs_primitive(&self, filter: &Filter) -> eyre::Result<Vec<Log>> {
+        self.provider.fetch_logs_primitive(filter).await
+    }
+
+    async fn view_call<IC>(
+        &self,
+        block_id: BlockId,
+        contract: Address,
+        call: IC
+    ) -> eyre::Result<IC::Return>
+    where
+        IC: SolCall + Send + std::fmt::Debug
+    {
+        self.provider.view_call(block_id, contract, call).await
+    }
+
+    async fn view_deploy_call<IC>(
+        &self,
+        block_id: BlockId,
+        tx: <N as Network>::TransactionRequest
+    ) -> eyre::Result<IC::RustType>
+    where
+        IC: SolType + Send
+    {
+        self.provider.view_deploy_call::<IC>(block_id, tx).await
+    }
+
+    async fn account_info_primitive(
+        &self,
+        address: Address,
+        block_id: BlockId
+    ) -> eyre::Result<AccountInfo> {
+        // the code is read at the trusted block, which `block_id` may not pin
+        let (number, proof) = self.verified_proof(address, Vec::new(), block_id).await?;
+        let account = trie_account(&proof);
+        let code = self
+            .provider
+            .get_code_at(address)
+            .block_id(number.into())
+            .await?;
+        if keccak256(&code) != account.code_hash {
+            return Err(eyre::eyre!(
+                "code of {address:?} does not match its verified code hash {:?}",
+                account.code_hash
+            ));
+        }
+
+        Ok(AccountInfo { balance: account.balance, nonce: account.nonce, code })
+    }
+
+    async fn alloy_root_provider(&self) -> eyre::Result<RootProvider<N>> {
+        self.provider.alloy_root_provider().await
+    }
+
+    async fn block_number_from_block_id(&self, block_id: BlockId) -> eyre::Result<u64> {
+        self.provider.block_number_from_block_id(block_id).await
+    }
+
+    async fn fetch_block_primitive(
+        &self,
+        block_id: BlockId,
+        full: bool
+    ) -> eyre::Result<<N as Network>::BlockResponse> {
+        self.provider.fetch_block_primitive(block_id, full).await
+    }
+
+    async fn tx_success_primitive(&self, tx_hash: TxHash) -> eyre::Result<bool> {
+        self.provider.tx_success_primitive(tx_hash).await
+    }
+
+    async fn tx_by_hash_primitive(
+        &self,
+        tx_hash: TxHash
+    ) -> eyre::Result<Option<<N as Network>::TransactionResponse>> {
+        self.provider.tx_by_hash_primitive(tx_hash).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::U256;
+    use alloy_trie::{HashBuilder, proof::ProofRetainer};
+
+    use super::*;
+
+    /// A storage trie holding `slots`, and the proof of `target`.
+    fn storage_trie(slots: &[(B256, U256)], target: B256) -> (B256, EIP1186StorageProof) {
+        let target_path = Nibbles::unpack(keccak256(target));
+        let mut leaves = slots
+            .iter()
+            .map(|(slot, value)| (Nibbles::unpack(keccak256(slot)), alloy_rlp::encode(value)))
+            .collect::<Vec<_>>();
+        leaves.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::from_iter([target_path]));
+        for (path, value) in &leaves {
+            builder.add_leaf(*path, value);
+        }
+        let root = builder.root();
+
+        let proof = builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect();
+        let value = slots
+            .iter()
+            .find(|(slot, _)| *slot == target)
+            .map(|(_, value)| *value)
+            .unwrap_or_default();
+
+        (root, EIP1186StorageProof { key: target.into(), value, proof })
+    }
+
+    #[test]
+    fn test_verify_slot_proof() {
+        let slots = [
+            (B256::with_last_byte(1), U256::from(100)),
+            (B256::with_last_byte(2), U256::from(200)),
+            (B256::with_last_byte(3), U256::from(300))
+        ];
+
+        let (root, proof) = storage_trie(&slots, B256::with_last_byte(2));
+        assert!(verify_slot_proof(root, &proof).is_ok());
+
+        let lie = EIP1186StorageProof { value: U256::from(201), ..proof.clone() };
+        assert!(verify_slot_proof(root, &lie).is_err());
+
+        let omitted = EIP1186StorageProof { value: U256::ZERO, ..proof };
+        assert!(verify_slot_proof(root, &omitted).is_err());
+    }
+
+    #[cfg(feature = "l1")]
+    #[tokio::test]
+    async fn test_verified_angstrom_fees() {
+        use crate::l1::{
+            AngstromL1Chain,
+            apis::AngstromL1UserApi,
+            test_utils::{spawn_angstrom_api, valid_test_params::init_valid_position_params}
+        };
+
+        let pos_info = init_valid_position_params();
+        let provider = spawn_angstrom_api().await.unwrap().eth_provider().clone();
+        let verified = VerifiedStorageFetcher::new(provider.clone(), provider.clone());
+
+        let direct_fees = provider
+            .angstrom_fees(
+                pos_info.pool_id,
+                pos_info.current_pool_tick,
+                pos_info.position_token_id,
+                pos_info.tick_lower,
+                pos_info.tick_upper,
+                pos_info.block_number.into(),
+                AngstromL1Chain::Mainnet
+            )
+            .await
+            .unwrap();
+        let verified_fees = verified
+            .angstrom_fees(
+                pos_info.pool_id,
+                pos_info.current_pool_tick,
+                pos_info.position_token_id,
+                pos_info.tick_lower,
+                pos_info.tick_upper,
+                pos_info.block_number.into(),
+                AngstromL1Chain::Mainnet
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(direct_fees, verified_fees);
+    }
+}
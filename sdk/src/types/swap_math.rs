@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use alloy_primitives::{U256, uint};
+use uni_v4::tick_info::TickInfo;
+use uniswap_storage::v4::utils::mul_div;
+
+use super::liquidity_amounts::{Q96, sqrt_price_at_tick};
+
+pub const MIN_SQRT_PRICE: U256 = uint!(4295128739_U256);
+pub const MAX_SQRT_PRICE: U256 = uint!(1461446703485210103287273052203988822378723970342_U256);
+
+const FEE_PIPS_DENOMINATOR: u32 = 1_000_000;
+
+/// The result of [`swap_exact_in`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapExactIn {
+    pub amount_out:        U256,
+    /// whether the swap moved past the last of the loaded initialized ticks
+    /// in its direction, where ticks that were not loaded are missed
+    pub past_loaded_ticks: bool
+}
+
+/// The output of an exact input swap of `amount_in` through the pool, as
+/// computed by uniswap's v4 pool. `fee_pips` is the pool's LP fee in
+/// hundredths of a bip. Past the last of the loaded ticks the swap keeps its
+/// liquidity, which is flagged in the result.
+pub fn swap_exact_in(
+    sqrt_price_x96: U256,
+    tick: i32,
+    liquidity: u128,
+    ticks: &HashMap<i32, TickInfo>,
+    zero_for_one: bool,
+    amount_in: U256,
+    fee_pips: u32
+) -> SwapExactIn {
+    let mut initialized_ticks = ticks
+        .iter()
+        .filter(|(_, info)| info.initialized)
+        .map(|(tick, info)| (*tick, info.liquidity_net))
+        .collect::<Vec<_>>();
+    initialized_ticks.sort_by_key(|(tick, _)| *tick);
+    if zero_for_one {
+        initialized_ticks.retain(|(t, _)| *t <= tick);
+        initialized_ticks.reverse();
+    } else {
+        initialized_ticks.retain(|(t, _)| *t > tick);
+    }
+    let mut next_ticks = initialized_ticks.into_iter();
+
+    let price_limit =
+        if zero_for_one { MIN_SQRT_PRICE + U256::from(1) } else { MAX_SQRT_PRICE - U256::from(1) };
+    let (mut sqrt_price, mut liquidity) = (sqrt_price_x96, liquidity);
+    let (mut amount_remaining, mut amount_out) = (amount_in, U256::ZERO);
+    let mut past_loaded_ticks = false;
+
+    while !amount_remaining.is_zero() && sqrt_price != price_limit {
+        let next_tick = next_ticks.next();
+        past_loaded_ticks |= next_tick.is_none();
+        let sqrt_target = next_tick
+            .map(|(tick, _)| sqrt_price_at_tick(tick))
+            .filter(
+                |target| if zero_for_one { *target > price_limit } else { *target < price_limit }
+            )
+            .unwrap_or(price_limit);
+
+        let step = swap_step(sqrt_price, sqrt_target, liquidity, amount_remaining, fee_pips);
+        sqrt_price = step.sqrt_price_next;
+        amount_remaining -= step.amount_in + step.fee_amount;
+        amount_out += step.amount_out;
+
+        if let Some((_, liquidity_net)) = next_tick.filter(|_| sqrt_price == sqrt_target) {
+            let liquidity_net = if zero_for_one { -liquidity_net } else { liquidity_net };
+            liquidity = liquidity.saturating_add_signed(liquidity_net);
+        }
+    }
+
+    SwapExactIn { amount_out, past_loaded_ticks }
+}
+
+struct SwapStep {
+    sqrt_price_next: U256,
+    amount_in:       U256,
+    amount_out:      U256,
+    fee_amount:      U256
+}
+
+fn swap_step(
+    sqrt_price_current: U256,
+    sqrt_price_target: U256,
+    liquidity: u128,
+    amount_remaining: U256,
+    fee_pips: u32
+) -> SwapStep {
+    let zero_for_one = sqrt_price_current >= sqrt_price_target;
+    let fee_complement = U256::from(FEE_PIPS_DENOMINATOR - fee_pips);
+
+    let amount_remaining_less_fee =
+        mul_div(amount_remaining, fee_complement, U256::from(FEE_PIPS_DENOMINATOR));
+    let amount_in_to_target = if zero_for_one {
+        amount0_delta(sqrt_price_target, sqrt_price_current, liquidity, true)
+    } else {
+        amount1_delta(sqrt_price_current, sqrt_price_target, liquidity, true)
+    };
+
+    let (sqrt_price_next, amount_in, fee_amount) = if amount_remaining_less_fee
+        >= amount_in_to_target
+    {
+        let fee_amount = if fee_pips == FEE_PIPS_DENOMINATOR {
+            amount_in_to_target
+        } else {
+            mul_div_rounding_up(amount_in_to_target, U256::from(fee_pips), fee_complement)
+        };
+        (sqrt_price_target, amount_in_to_target, fee_amount)
+    } else {
+        let sqrt_price_next = next_sqrt_price_from_input(
+            sqrt_price_current,
+            liquidity,
+            amount_remaining_less_fee,
+            zero_for_one
+        );
+        (sqrt_price_next, amount_remaining_less_fee, amount_remaining - amount_remaining_less_fee)
+    };
+
+    let amount_out = if zero_for_one {
+        amount1_delta(sqrt_price_next, sqrt_price_current, liquidity, false)
+    } else {
+        amount0_delta(sqrt_price_current, sqrt_price_next, liquidity, false)
+    };
+
+    SwapStep { sqrt_price_next, amount_in, amount_out, fee_amount }
+}
+
+fn next_sqrt_price_from_input(
+    sqrt_price: U256,
+    liquidity: u128,
+    amount_in: U256,
+    zero_for_one: bool
+) -> U256 {
+    if amount_in.is_zero() {
+        return sqrt_price;
+    }
+
+    if zero_for_one {
+        let numerator = U256::from(liquidity) << 96;
+        if let Some(denominator) = amount_in
+            .checked_mul(sqrt_price)
+            .and_then(|product| numerator.checked_add(product))
+        {
+            return mul_div_rounding_up(numerator, sqrt_price, denominator);
+        }
+        div_rounding_up(numerator, numerator / sqrt_price + amount_in)
+    } else {
+        let quotient = if amount_in < U256::from(1) << 160 {
+            (amount_in << 96) / U256::from(liquidity)
+        } else {
+            mul_div(amount_in, Q96, U256::from(liquidity))
+        };
+        sqrt_price + quotient
+    }
+}
+
+fn amount0_delta(sqrt_price_a: U256, sqrt_price_b: U256, liquidity: u128, round_up: bool) -> U256 {
+    let (lower, upper) = if sqrt_price_a < sqrt_price_b {
+        (sqrt_price_a, sqrt_price_b)
+    } else {
+        (sqrt_price_b, sqrt_price_a)
+    };
+    let numerator = U256::from(liquidity) << 96;
+
+    if round_up {
+        div_rounding_up(mul_div_rounding_up(numerator, upper - lower, upper), lower)
+    } else {
+        mul_div(numerator, upper - lower, upper) / lower
+    }
+}
+
+fn amount1_delta(sqrt_price_a: U256, sqrt_price_b: U256, liquidity: u128, round_up: bool) -> U256 {
+    let (lower, upper) = if sqrt_price_a < sqrt_price_b {
+        (sqrt_price_a, sqrt_price_b)
+    } else {
+        (sqrt_price_b, sqrt_price_a)
+    };
+
+    if round_up {
+        mul_div_rounding_up(U256::from(liquidity), upper - lower, Q96)
+    } else {
+        mul_div(U256::from(liquidity), upper - lower, Q96)
+    }
+}
+
+fn mul_div_rounding_up(a: U256, b: U256, denominator: U256) -> U256 {
+    let result = mul_div(a, b, denominator);
+    if a.mul_mod(b, denominator).is_zero() { result } else { result + U256::from(1) }
+}
+
+fn div_rounding_up(a: U256, b: U256) -> U256 {
+    let result = a / b;
+    if (a % b).is_zero() { result } else { result + U256::from(1) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::liquidity_amounts::amounts_for_liquidity;
+
+    fn tick(liquidity_net: i128) -> TickInfo {
+        TickInfo { initialized: true, liquidity_net, liquidity_gross: liquidity_net.unsigned_abs() }
+    }
+
+    #[test]
+    fn test_swap_within_range() {
+        let liquidity = 1_000_000_000_000_000_000u128;
+        let ticks =
+            HashMap::from([(-600, tick(liquidity as i128)), (600, tick(-(liquidity as i128)))]);
+        let amount_in = U256::from(1_000_000u64);
+
+        let swap0 = swap_exact_in(Q96, 0, liquidity, &ticks, false, amount_in, 0);
+        let swap1 = swap_exact_in(Q96, 0, liquidity, &ticks, true, amount_in, 0);
+        assert!(!swap0.past_loaded_ticks && !swap1.past_loaded_ticks);
+        let (out0, out1) = (swap0.amount_out, swap1.amount_out);
+        // at a price of 1 a small swap gets back almost all of its input
+        assert!(out0 < amount_in && amount_in - out0 <= U256::from(1));
+        assert!(out1 < amount_in && amount_in - out1 <= U256::from(1));
+
+        let with_fee = swap_exact_in(Q96, 0, liquidity, &ticks, true, amount_in, 3000);
+        assert!(with_fee.amount_out < out1);
+    }
+
+    #[test]
+    fn test_swap_through_range() {
+        let liquidity = 1_000_000_000_000_000_000u128;
+        let ticks =
+            HashMap::from([(-600, tick(liquidity as i128)), (600, tick(-(liquidity as i128)))]);
+
+        // more than the range holds drains all of its token1
+        let swap =
+            swap_exact_in(Q96, 0, liquidity, &ticks, true, U256::from(10).pow(U256::from(30)), 0);
+        let (_, held1) = amounts_for_liquidity(Q96, -600, 600, liquidity);
+        assert_eq!(swap.amount_out, held1);
+        assert!(swap.past_loaded_ticks);
+    }
+}
@@ -1,5 +1,5 @@
 use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, B256};
 use alloy_rpc_types::Filter;
 use alloy_sol_types::SolEvent;
 use angstrom_types_primitives::contract_bindings::pool_manager::PoolManager;
@@ -24,6 +24,21 @@ pub(crate) fn historical_pool_manager_swap_filter(
         .collect()
 }
 
+/// [`historical_pool_manager_swap_filter`] narrowed to the swaps of `pool_ids`
+#[cfg(feature = "l2")]
+pub(crate) fn historical_pools_swap_filter(
+    start_block: Option<u64>,
+    end_block: Option<u64>,
+    pool_manager_address: Address,
+    pool_ids: Vec<B256>,
+    deploy_block: u64
+) -> Vec<Filter> {
+    historical_pool_manager_swap_filter(start_block, end_block, pool_manager_address, deploy_block)
+        .into_iter()
+        .map(|filter| filter.topic1(pool_ids.clone()))
+        .collect()
+}
+
 pub(crate) fn historical_pool_manager_modify_liquidity_filter(
     start_block: Option<u64>,
     end_block: Option<u64>,
@@ -44,6 +59,50 @@ pub(crate) fn historical_pool_manager_modify_liquidity_filter(
         .collect()
 }
 
+#[cfg(feature = "l2")]
+pub(crate) fn historical_contract_events_filter(
+    start_block: Option<u64>,
+    end_block: Option<u64>,
+    addresses: Vec<Address>,
+    event_signatures: Vec<B256>,
+    deploy_block: u64
+) -> Vec<Filter> {
+    chunk_blocks(start_block, end_block, deploy_block)
+        .into_iter()
+        .map(|(s, e)| {
+            Filter::new()
+                .event_signature(event_signatures.clone())
+                .address(addresses.clone())
+                .from_block(s)
+                .to_block(e)
+        })
+        .collect()
+}
+
+/// The ERC721 transfers of the PositionManager's tokens to `to`.
+#[cfg(all(feature = "l1", feature = "l2"))]
+pub(crate) fn historical_position_transfers_filter(
+    start_block: Option<u64>,
+    end_block: Option<u64>,
+    position_manager_address: Address,
+    to: Address,
+    deploy_block: u64
+) -> Vec<Filter> {
+    let transfer_event = crate::l1::types::PositionTransfer::SIGNATURE_HASH;
+
+    chunk_blocks(start_block, end_block, deploy_block)
+        .into_iter()
+        .map(|(s, e)| {
+            Filter::new()
+                .event_signature(transfer_event)
+                .address(position_manager_address)
+                .topic2(to.into_word())
+                .from_block(s)
+                .to_block(e)
+        })
+        .collect()
+}
+
 pub(crate) fn chunk_blocks(
     start_block: Option<u64>,
     end_block: Option<u64>,